@@ -0,0 +1,109 @@
+//! Serializing/deserializing arrays of primitives element by element, as serde and `deserialize_seq` do,
+//! against the bulk path used by `NetStruct` for arrays and vectors of primitives.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use net_struct_serde::{traits::*, *};
+
+const LEN: usize = 1024;
+
+#[derive(Clone, Debug, NetStruct)]
+pub struct Capture {
+    pub id: u32,
+    pub payload_len: u16,
+    #[net_struct(vec_len(payload_len, bytes))]
+    pub payload: [u8; LEN],
+    pub samples: [u16; LEN],
+    pub timestamps: [u64; 64],
+}
+
+fn capture() -> Capture {
+    Capture {
+        id: 7,
+        payload_len: LEN as u16,
+        payload: core::array::from_fn(|i| i as u8),
+        samples: core::array::from_fn(|i| i as u16),
+        timestamps: core::array::from_fn(|i| i as u64 * 1_000_000),
+    }
+}
+
+fn serialize<T: Serialize + ?Sized>(value: &T, buf: &mut [u8]) -> usize {
+    let mut serializer = NetStructSerializer::new(buf);
+    value.serialize(&mut serializer).unwrap();
+    serializer.finalize()
+}
+
+fn bench_serialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("serialize");
+    let mut buf = vec![0u8; 16 * LEN];
+    let bytes: [u8; LEN] = core::array::from_fn(|i| i as u8);
+    let words: [u16; LEN] = core::array::from_fn(|i| i as u16);
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.bench_function("u8 element-wise", |b| {
+        b.iter(|| serialize(black_box(&bytes[..]), &mut buf))
+    });
+    group.bench_function("u8 bulk", |b| {
+        b.iter(|| serialize(&PrimitiveSeq(black_box(&bytes[..])), &mut buf))
+    });
+    group.throughput(Throughput::Bytes(2 * LEN as u64));
+    group.bench_function("u16 element-wise", |b| {
+        b.iter(|| serialize(black_box(&words[..]), &mut buf))
+    });
+    group.bench_function("u16 bulk", |b| {
+        b.iter(|| serialize(&PrimitiveSeq(black_box(&words[..])), &mut buf))
+    });
+    let s = capture();
+    group.throughput(Throughput::Bytes(encoded_len(&s).unwrap() as u64));
+    group.bench_function("NetStruct", |b| {
+        b.iter(|| serialize(black_box(&s), &mut buf))
+    });
+    group.finish();
+}
+
+fn bench_deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("deserialize");
+    let data: Vec<u8> = (0..16 * LEN).map(|i| i as u8).collect();
+    group.throughput(Throughput::Bytes(LEN as u64));
+    group.bench_function("u8 element-wise", |b| {
+        b.iter(|| {
+            let mut arr = [0u8; LEN];
+            let mut d = NetStructDeserializer::new(black_box(&data[..]));
+            (&mut d).deserialize_seq(&mut arr, LEN).unwrap();
+            arr
+        })
+    });
+    group.bench_function("u8 bulk", |b| {
+        b.iter(|| {
+            let mut arr = [0u8; LEN];
+            let mut d = NetStructDeserializer::new(black_box(&data[..]));
+            (&mut d).deserialize_primitive_seq(&mut arr, LEN).unwrap();
+            arr
+        })
+    });
+    group.throughput(Throughput::Bytes(2 * LEN as u64));
+    group.bench_function("u16 element-wise", |b| {
+        b.iter(|| {
+            let mut arr = [0u16; LEN];
+            let mut d = NetStructDeserializer::new(black_box(&data[..]));
+            (&mut d).deserialize_seq(&mut arr, LEN).unwrap();
+            arr
+        })
+    });
+    group.bench_function("u16 bulk", |b| {
+        b.iter(|| {
+            let mut arr = [0u16; LEN];
+            let mut d = NetStructDeserializer::new(black_box(&data[..]));
+            (&mut d).deserialize_primitive_seq(&mut arr, LEN).unwrap();
+            arr
+        })
+    });
+    let mut buf = vec![0u8; 16 * LEN];
+    let len = serialize(&capture(), &mut buf);
+    group.throughput(Throughput::Bytes(len as u64));
+    group.bench_function("NetStruct", |b| {
+        b.iter(|| from_slice::<Capture>(black_box(&buf[..len])).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_serialize, bench_deserialize);
+criterion_main!(benches);
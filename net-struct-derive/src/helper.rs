@@ -1,6 +1,3 @@
-use proc_macro2::token_stream::IntoIter;
-use std::iter::Peekable;
-
 pub(crate) fn parse_attr<F>(attrs: &Vec<syn::Attribute>, attr_path: &'static str, mut f: F)
 where
     F: FnMut(&proc_macro2::TokenStream),
@@ -43,46 +40,69 @@ where
     }
 }
 
-pub(crate) fn consume_punct<I>(it: &mut Peekable<I>, punct: char) -> Option<char>
+pub(crate) fn expect_punct<I>(it: &mut I, punct: char, expect_msg: &str)
 where
     I: Iterator<Item = proc_macro2::TokenTree>,
 {
     use proc_macro2::TokenTree::*;
-    let mut consumed = false;
-    if let Some(Punct(p)) = it.peek() {
-        consumed = p.as_char() == punct;
+    match it.next() {
+        Some(Punct(p)) if p.as_char() == punct => (),
+        _ => panic!("{}", expect_msg),
+    }
+}
+
+/// splits the arguments of an attribute at every top level comma, commas that do not separate
+/// two arguments (e.g. in `flavour = FixedPoint<i16, 8>`) are kept in the argument
+pub(crate) fn split_attr_args(ts: &proc_macro2::TokenStream) -> Vec<proc_macro2::TokenStream> {
+    use proc_macro2::TokenTree::*;
+    fn is_arg_start(part: &[proc_macro2::TokenTree]) -> bool {
+        match (part.first(), part.get(1)) {
+            (Some(Ident(_)), None | Some(Group(_))) => true,
+            (Some(Ident(_)), Some(Punct(p))) => p.as_char() == '=',
+            _ => false,
+        }
+    }
+    let mut parts: Vec<Vec<proc_macro2::TokenTree>> = vec![Vec::new()];
+    for tt in ts.clone() {
+        match &tt {
+            Punct(p) if p.as_char() == ',' => parts.push(Vec::new()),
+            _ => parts.last_mut().unwrap().push(tt),
+        }
     }
-    match consumed {
-        true => {
-            let _ = it.next()?;
-            Some(punct)
+    let mut args: Vec<Vec<proc_macro2::TokenTree>> = Vec::new();
+    for part in parts.into_iter().filter(|p| !p.is_empty()) {
+        match args.last_mut() {
+            Some(arg) if !is_arg_start(&part) => {
+                arg.push(Punct(proc_macro2::Punct::new(',', proc_macro2::Spacing::Alone)));
+                arg.extend(part);
+            }
+            _ => args.push(part),
         }
-        false => None,
     }
+    args.into_iter()
+        .map(proc_macro2::TokenStream::from_iter)
+        .collect()
 }
 
-pub(crate) fn consume_ident<I>(it: &mut Peekable<I>) -> Option<String>
+/// parses the `(<ARG>)` part of an attribute
+pub(crate) fn parse_attr_group_arg<I>(it: &mut I, attr_path: &str) -> String
 where
     I: Iterator<Item = proc_macro2::TokenTree>,
 {
-    use proc_macro2::TokenTree::*;
-    let mut s = None;
-    if let Some(Ident(i)) = it.peek() {
-        s = Some(i.to_string());
-    }
-    if s.is_some() {
-        let _ = it.next()?;
-    }
-    s
+    let expect_group_msg = format!(
+        "Expected parenthesis with an argument after \"{}\"",
+        attr_path
+    );
+    let arg = expect_group(it, proc_macro2::Delimiter::Parenthesis, expect_group_msg.as_str());
+    assert!(!arg.is_empty(), "{}", expect_group_msg);
+    arg.to_string()
 }
 
-pub(crate) fn skip_until_punct(it: &mut Peekable<IntoIter>, punct: char) -> Peekable<IntoIter> {
-    use proc_macro2::TokenTree::*;
-    let new_it = it.skip_while(|tt| match tt {
-        Punct(p) => p.as_char() != punct,
-        _ => true,
-    });
-    proc_macro2::TokenStream::from_iter(new_it)
-        .into_iter()
-        .peekable()
+/// integers and floats, whose arrays are serialized/deserialized in bulk
+const PRIMITIVE_TYPES: [&'static str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+];
+
+pub(crate) fn is_primitive(ty: &proc_macro2::TokenStream) -> bool {
+    PRIMITIVE_TYPES.contains(&ty.to_string().as_str())
 }
@@ -1,7 +1,9 @@
 pub(crate) mod helper;
 mod net_enum;
+mod net_flags;
 mod net_struct;
 use net_enum::*;
+use net_flags::*;
 use net_struct::*;
 use syn::DeriveInput;
 mod err;
@@ -36,6 +38,11 @@ mod test;
 /// - `net_struct_serde::traits::Deserialize`
 ///   - `Sized`::
 /// - `core::cmp::Eq`
+/// - `net_struct_serde::traits::Placeholder`
+/// - `net_struct_serde::traits::ZeroCopy` with `#[net_struct(zerocopy)]`
+/// - `net_struct_serde::traits::FixedLen` with `#[net_struct(zerocopy)]` or `#[net_struct(view)]` and without vectors
+///
+/// and the view `<STRUCT>View` with `#[net_struct(view)]`
 #[proc_macro_derive(NetStruct, attributes(net_struct))]
 pub fn derive_net_struct(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let a: DeriveInput = syn::parse(item.clone()).unwrap();
@@ -66,6 +73,10 @@ pub fn derive_net_struct(item: proc_macro::TokenStream) -> proc_macro::TokenStre
 /// - `net_struct_serde::traits::Deserialize`
 /// - `Into<T>`: where `T` is the type representation of the enumeration
 /// - `TryFrom<T>`: where `T` is the type representation of the enumeration
+/// - `net_struct_serde::traits::Placeholder`: the first variant
+/// - `core::fmt::Display`: the name of the variant
+///
+/// together with `VARIANTS`, `name()` and `from_name()`
 #[proc_macro_derive(NetEnum, attributes(net_enum))]
 pub fn derive_net_enum(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let a: DeriveInput = syn::parse(item.clone()).unwrap();
@@ -80,3 +91,30 @@ pub fn derive_net_enum(item: proc_macro::TokenStream) -> proc_macro::TokenStream
         _ => panic!("Expected a enum"),
     }
 }
+
+/// usage:
+/// ```
+/// #[derive(Clone, Copy, PartialEq, Eq, NetFlags)]
+/// #[net_flags(FIN = 0x01, SYN = 0x02, RST = 0x04)]
+/// struct TcpFlags(u8);
+/// ```
+/// Creates an implementation of the following traits for the attached tuple structure:
+/// - `net_struct_serde::traits::NetFlags`
+/// - `serde::Serialize`
+/// - `net_struct_serde::traits::Deserialize`: the unknown bits are kept, or rejected with `strict`
+/// - `core::fmt::Debug`: the names of the flags that are set, e.g. `TcpFlags(FIN | SYN)`
+/// - `core::ops::{BitOr, BitAnd, BitXor, Sub, Not}` and their assignments
+/// - `net_struct_serde::traits::Placeholder`: the empty set
+///
+/// together with a constant for each flag and the methods of a set (`contains`, `insert`, ..)
+#[proc_macro_derive(NetFlags, attributes(net_flags))]
+pub fn derive_net_flags(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let a: DeriveInput = syn::parse(item.clone()).unwrap();
+    match &a.data {
+        syn::Data::Struct(_) => match NetFlags::derive_input_to_token_stream(a) {
+            Ok(ts) => proc_macro::TokenStream::from(ts),
+            Err(e) => panic!("{:?}", e),
+        },
+        _ => panic!("Expected a struct"),
+    }
+}
@@ -7,12 +7,17 @@ use syn::{Data, DeriveInput};
 use variants::NetEnumVariants;
 mod impl_de;
 mod impl_into;
+mod impl_names;
+mod impl_placeholder;
 mod impl_ser;
 mod impl_tryfrom;
 mod variants;
 
 const ATTR_PATH: &'static str = "net_enum";
 const DISCRIMINANT_TYPE_PATH: &'static str = "repr";
+const INTEGER_TYPES: [&'static str; 12] = [
+    "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+];
 
 #[derive(Clone)]
 pub(super) struct NetEnum {
@@ -33,11 +38,7 @@ impl From<DeriveInput> for NetEnum {
         };
         let mut ns = Self {
             derive_input: di.clone(),
-            variants: ds
-                .variants
-                .iter()
-                .map(|f| Rc::new(NetEnumVariants::from(f)))
-                .collect(),
+            variants: Vec::new(),
             attrs: NetEnumAttr {
                 repr: TokenStream::new(),
             },
@@ -45,10 +46,48 @@ impl From<DeriveInput> for NetEnum {
         parse_attr(&di.attrs, ATTR_PATH, |tokens| {
             ns.parse_attr_discriminant_size(tokens);
         });
+        // the repr defaults to the integer of `#[repr(..)]`
+        if ns.attrs.repr.is_empty() {
+            parse_attr(&di.attrs, DISCRIMINANT_TYPE_PATH, |tokens| {
+                if let Some(ty) = tokens.clone().into_iter().find(|t| INTEGER_TYPES.contains(&t.to_string().as_str())) {
+                    ns.attrs.repr = ty.into();
+                }
+            });
+        }
+        let mut variants: Vec<NetEnumVariants> = ds.variants.iter().map(NetEnumVariants::from).collect();
+        if !ns.is_array_repr() {
+            infer_discriminants(&mut variants);
+        }
+        ns.variants = variants.into_iter().map(Rc::new).collect();
         ns
     }
 }
 
+/// the variants without a discriminant take the one of the previous variant plus one, or `0` for the first
+/// variant, as in Rust (a variant with a range counts although it has no discriminant)
+fn infer_discriminants(variants: &mut [NetEnumVariants]) {
+    let mut prev: Option<TokenStream> = None;
+    let mut offset = 0usize;
+    for v in variants.iter_mut() {
+        match &v.discriminant {
+            Some(d) => {
+                prev = Some(d.clone());
+                offset = 1;
+            }
+            None => {
+                let offset_lit = Literal::usize_unsuffixed(offset);
+                if v.range.is_none() {
+                    v.discriminant = Some(match &prev {
+                        Some(p) => quote!((#p) + #offset_lit),
+                        None => quote!(#offset_lit),
+                    });
+                }
+                offset += 1;
+            }
+        }
+    }
+}
+
 impl NetEnum {
     pub fn derive_input_to_token_stream(di: DeriveInput) -> Result<TokenStream, DeriveErr> {
         Self::from(di).into()
@@ -74,14 +113,93 @@ impl NetEnum {
     }
 }
 
+impl NetEnum {
+    /// the enumeration is serialized into an array, e.g. `repr([u8; 4])`, rather than an integer
+    fn is_array_repr(&self) -> bool {
+        matches!(self.attrs.repr.clone().into_iter().next(), Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket)
+    }
+
+    /// the enumeration is `traits::FixedLen` if the repr is an integer or an array of integers
+    fn impl_fixed_len(&self) -> TokenStream {
+        let is_integer = |ts: TokenStream| INTEGER_TYPES.contains(&ts.to_string().as_str());
+        let elem = match self.attrs.repr.clone().into_iter().next() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Bracket => {
+                g.stream().into_iter().take_while(|t| !matches!(t, TokenTree::Punct(p) if p.as_char() == ';')).collect()
+            }
+            _ => self.attrs.repr.clone(),
+        };
+        if !is_integer(elem) {
+            return TokenStream::new();
+        }
+        let enum_name = &self.derive_input.ident;
+        let ty = &self.attrs.repr;
+        quote! {
+            impl net_struct_serde::traits::FixedLen for #enum_name {
+                const LEN: usize = <#ty as net_struct_serde::traits::FixedLen>::LEN;
+            }
+        }
+    }
+
+    /// only the variants with a range hold a value, and the values of an array `repr` cannot be ranges
+    /// nor be declared twice (the overlaps of integers are checked at compile time by `check_overlaps`)
+    fn check_variants(&self) -> Result<(), DeriveErr> {
+        if self.is_array_repr() {
+            let mut values = Vec::new();
+            for var in &self.variants {
+                if var.range.is_some() {
+                    return Err(DeriveErr::Message(format!(
+                        "The variant \"{}\" of \"{}\" cannot have a range since the repr is an array",
+                        var.ident, self.derive_input.ident
+                    )));
+                }
+                for v in var.discriminant.iter().chain(var.aliases.iter()) {
+                    let v = v.to_string();
+                    if values.contains(&v) {
+                        return Err(DeriveErr::Message(format!(
+                            "The value {} of \"{}\" is declared twice",
+                            v, self.derive_input.ident
+                        )));
+                    }
+                    values.push(v);
+                }
+            }
+        }
+        for var in &self.variants {
+            let msg = match (&var.range, var.fields, &var.discriminant, var.aliases.is_empty()) {
+                (Some(_), 1, None, true) | (None, 0, _, _) => continue,
+                (Some(_), 1, None, false) => "cannot have aliases since it has a range",
+                (Some(_), 1, Some(_), _) => "cannot have a discriminant since it has a range",
+                (Some(_), _, _, _) => "must hold exactly one value since it has a range",
+                (None, _, _, _) => "cannot hold values without a range",
+            };
+            return Err(DeriveErr::Message(format!(
+                "The variant \"{}\" of \"{}\" {}",
+                var.ident, self.derive_input.ident, msg
+            )));
+        }
+        Ok(())
+    }
+}
+
 impl Into<Result<TokenStream, DeriveErr>> for NetEnum {
     fn into(self) -> Result<TokenStream, DeriveErr> {
         let mut ts = TokenStream::new();
         let enum_name = &self.derive_input.ident;
+        if self.attrs.repr.is_empty() {
+            return Err(DeriveErr::Message(format!(
+                "Expected \"#[{}({}(<TYPE>))]\" or \"#[{}(<INTEGER_TYPE>)]\" for \"{}\"",
+                ATTR_PATH, DISCRIMINANT_TYPE_PATH, DISCRIMINANT_TYPE_PATH, enum_name
+            )));
+        }
+        self.check_variants()?;
+        ts.extend(self.check_overlaps());
         ts.extend(self.impl_into()?);
         ts.extend(self.impl_tryfrom()?);
         ts.extend(self.impl_serialize()?);
         ts.extend(self.impl_deserialize()?);
+        ts.extend(self.impl_placeholder()?);
+        ts.extend(self.impl_names()?);
+        ts.extend(self.impl_fixed_len());
         ts.extend(quote! {
             impl NetEnum for #enum_name {}
         });
@@ -18,7 +18,7 @@ impl NetEnum {
                     where D: net_struct_serde::traits::Deserializer
                 {
                     use net_struct_serde::traits::Deserialize;
-                    let #var = #ty::deserialize(deserializer)?;
+                    let #var = <#ty as Deserialize>::deserialize(deserializer)?;
                     Self::try_from(#var)
                 }
             }
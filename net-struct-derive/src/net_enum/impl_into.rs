@@ -6,20 +6,35 @@ use crate::err::DeriveErr;
 use super::NetEnum;
 
 impl NetEnum {
+    /// the match arms from the variants to their values, the value of a variant with a range is the one it holds
+    pub(super) fn value_arms(&self) -> TokenStream {
+        self.variants.iter().fold(TokenStream::new(), |mut acc, var| {
+            let i = &var.ident;
+            acc.extend(match (&var.range, &var.discriminant) {
+                (Some(_), _) => quote! { Self::#i(v) => v, },
+                (None, d) => quote! { Self::#i => #d, },
+            });
+            acc
+        })
+    }
+
     pub(super) fn impl_into(&self) -> Result<TokenStream, DeriveErr> {
         let ty = &self.attrs.repr;
         let enum_name = &self.derive_input.ident;
+        let arms = self.value_arms();
         Ok(quote! {
             impl Into<#ty> for #enum_name {
                 #[inline]
                 fn into(self) -> #ty {
-                    self as #ty
+                    self.const_into()
                 }
             }
             impl #enum_name {
                 #[inline]
                 pub const fn const_into(self) -> #ty {
-                    self as #ty
+                    match self {
+                        #arms
+                    }
                 }
             }
         })
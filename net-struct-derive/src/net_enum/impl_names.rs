@@ -0,0 +1,64 @@
+use proc_macro2::TokenStream;
+
+use crate::err::DeriveErr;
+use quote::quote;
+
+use super::NetEnum;
+
+impl NetEnum {
+    /// writes `VARIANTS`, `name`, `from_name` and `Display`, the variants with a range are not in `VARIANTS`
+    /// and cannot be given by `from_name` since they hold a value
+    pub(super) fn impl_names(&self) -> Result<TokenStream, DeriveErr> {
+        let enum_name = &self.derive_input.ident;
+        let units = self
+            .variants
+            .iter()
+            .filter(|v| v.range.is_none())
+            .map(|v| &v.ident)
+            .collect::<Vec<_>>();
+        let unit_names = units.iter().map(|i| i.to_string());
+        let name_arms = self.variants.iter().map(|v| {
+            let i = &v.ident;
+            let name = i.to_string();
+            match v.range {
+                Some(_) => quote! { Self::#i(_) => #name, },
+                None => quote! { Self::#i => #name, },
+            }
+        });
+        let display_arms = self.variants.iter().map(|v| {
+            let i = &v.ident;
+            match v.range {
+                Some(_) => quote! { Self::#i(v) => write!(f, "{}({})", self.name(), v), },
+                None => quote! { Self::#i => f.write_str(self.name()), },
+            }
+        });
+        Ok(quote! {
+            impl #enum_name {
+                /// the variants that do not hold a value
+                pub const VARIANTS: &'static [Self] = &[#(Self::#units),*];
+
+                /// the name of the variant
+                pub const fn name(&self) -> &'static str {
+                    match self {
+                        #(#name_arms)*
+                    }
+                }
+
+                /// the variant named `name`, if it does not hold a value
+                pub fn from_name(name: &str) -> Option<Self> {
+                    match name {
+                        #(#unit_names => Some(Self::#units),)*
+                        _ => None,
+                    }
+                }
+            }
+            impl core::fmt::Display for #enum_name {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    match self {
+                        #(#display_arms)*
+                    }
+                }
+            }
+        })
+    }
+}
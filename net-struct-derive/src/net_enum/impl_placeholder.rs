@@ -0,0 +1,31 @@
+use proc_macro2::TokenStream;
+
+use crate::err::DeriveErr;
+use quote::quote;
+
+use super::NetEnum;
+
+impl NetEnum {
+    pub(super) fn impl_placeholder(&self) -> Result<TokenStream, DeriveErr> {
+        let enum_name = &self.derive_input.ident;
+        let Some(first) = self.variants.first() else {
+            return Err(DeriveErr::Message(format!(
+                "The enumeration \"{}\" has no variants",
+                enum_name.to_string()
+            )));
+        };
+        // a variant with a range holds the start of the range
+        let value = match first.bounds(&self.attrs.repr).pop() {
+            Some((start, _)) if first.range.is_some() => quote!((#start)),
+            _ => TokenStream::new(),
+        };
+        let first = &first.ident;
+        Ok(quote! {
+            impl net_struct_serde::traits::Placeholder for #enum_name {
+                fn placeholder() -> Self {
+                    Self::#first #value
+                }
+            }
+        })
+    }
+}
@@ -9,11 +9,26 @@ impl NetEnum {
     pub(super) fn impl_serialize(&self) -> Result<TokenStream, DeriveErr> {
         let enum_name = &self.derive_input.ident;
         let ty = &self.attrs.repr;
+        // the value held by a variant with a range must be in the range
+        let checks = self.variants.iter().filter_map(|var| {
+            let range = var.range.as_ref()?;
+            let i = &var.ident;
+            let contains = range.contains(&quote!(*v));
+            let msg = format!("The value of \"{}::{}\" is out of its range", enum_name, i);
+            Some(quote! {
+                if let Self::#i(v) = self {
+                    if !#contains {
+                        return Err(<S::Error as serde::ser::Error>::custom(#msg));
+                    }
+                }
+            })
+        });
         Ok(quote! {
             impl net_struct_serde::traits::Serialize for #enum_name {
                 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
                     where S: net_struct_serde::traits::Serializer
                 {
+                    #(#checks)*
                     Into::<#ty>::into(self.clone()).serialize(serializer)
                 }
             }
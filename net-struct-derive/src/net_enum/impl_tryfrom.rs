@@ -1,18 +1,48 @@
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::quote;
 
 use crate::err::DeriveErr;
 
 use super::{NetEnum, NetEnumVariants};
 use std::rc::Rc;
 
+/// whether `ts` is an integer literal, which can be a pattern, e.g. `3` or `-1`
+fn is_literal(ts: &TokenStream) -> bool {
+    match syn::parse2::<syn::Expr>(ts.clone()) {
+        Ok(syn::Expr::Lit(_)) => true,
+        Ok(syn::Expr::Unary(syn::ExprUnary { op: syn::UnOp::Neg(_), expr, .. })) => {
+            matches!(*expr, syn::Expr::Lit(_))
+        }
+        _ => false,
+    }
+}
+
 impl NetEnum {
     fn var_impl_tryfrom(&self, var: &Rc<NetEnumVariants>) -> Result<TokenStream, DeriveErr> {
+        let i = &var.ident;
+        if let Some(range) = &var.range {
+            let contains = range.contains(&quote!(v));
+            return Ok(quote! { v if #contains => Ok(Self::#i(v)), });
+        }
         match var.discriminant {
+            // the values of an array are patterns, while integers may be any constant expression
+            Some(ref v) if self.is_array_repr() => {
+                let aliases = &var.aliases;
+                Ok(quote! { #v #(| #aliases)* => Ok(Self::#i), })
+            }
+            // the literals stay patterns, so that rustc rejects the unreachable ones, and the other
+            // values (e.g. the inferred discriminants) are compared in a guard
             Some(ref v) => {
-                let i = &var.ident;
-                let ts = v.to_token_stream();
-                Ok(quote! { #ts => Ok(Self::#i), })
+                let (literals, exprs): (Vec<_>, Vec<_>) =
+                    std::iter::once(v).chain(var.aliases.iter()).partition(|v| is_literal(v));
+                let mut ts = TokenStream::new();
+                if !literals.is_empty() {
+                    ts.extend(quote! { #(#literals)|* => Ok(Self::#i), });
+                }
+                if !exprs.is_empty() {
+                    ts.extend(quote! { v if #(v == (#exprs))||* => Ok(Self::#i), });
+                }
+                Ok(ts)
             }
             None => Err(DeriveErr::MissingDiscriminant(format!(
                 "Expected discriminant for the variant \"{}\" of \"{}\"",
@@ -25,18 +55,15 @@ impl NetEnum {
     pub(super) fn impl_tryfrom(&self) -> Result<TokenStream, DeriveErr> {
         let ty = &self.attrs.repr;
         let enum_name = &self.derive_input.ident;
-        let variants = self.variants.iter().fold(TokenStream::new(), |mut acc, i| {
-            acc.extend(self.var_impl_tryfrom(i));
-            acc
-        });
+        let mut variants = TokenStream::new();
+        for var in &self.variants {
+            variants.extend(self.var_impl_tryfrom(var)?);
+        }
         Ok(quote! {
             impl TryFrom<#ty> for #enum_name {
                 type Error = net_struct_serde::SerdeErr;
                 fn try_from(value: #ty) -> Result<Self, Self::Error> {
-                    match value {
-                        #variants
-                        _ => Err(net_struct_serde::SerdeErr::ParseFailed)
-                    }
+                    Self::const_try_from(value)
                 }
             }
             impl #enum_name {
@@ -49,4 +76,35 @@ impl NetEnum {
             }
         })
     }
+
+    /// checks at compile time that no value is deserialized into two variants
+    pub(super) fn check_overlaps(&self) -> TokenStream {
+        let ty = &self.attrs.repr;
+        if self.is_array_repr() {
+            return TokenStream::new();
+        }
+        let bounds = self.variants.iter().flat_map(|v| v.bounds(ty)).collect::<Vec<_>>();
+        if bounds.len() < 2 {
+            return TokenStream::new();
+        }
+        let (starts, ends): (Vec<_>, Vec<_>) = bounds.into_iter().unzip();
+        let msg = format!(
+            "The discriminants, aliases and ranges of \"{}\" overlap",
+            self.derive_input.ident
+        );
+        quote! {
+            const _: () = {
+                let bounds: &[(#ty, #ty)] = &[#((#starts, #ends)),*];
+                let mut i = 0;
+                while i < bounds.len() {
+                    let mut j = i + 1;
+                    while j < bounds.len() {
+                        assert!(bounds[i].1 < bounds[j].0 || bounds[j].1 < bounds[i].0, #msg);
+                        j += 1;
+                    }
+                    i += 1;
+                }
+            };
+        }
+    }
 }
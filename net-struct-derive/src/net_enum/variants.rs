@@ -1,16 +1,162 @@
-use quote::ToTokens;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+
+use crate::helper::*;
+
+use super::ATTR_PATH;
+
+const RANGE_ATTR_PATH: &'static str = "range";
+const ALIAS_ATTR_PATH: &'static str = "alias";
+const VALUE_ATTR_PATH: &'static str = "value";
+
+/// the bounds of `#[net_enum(range = <START>..=<END>)]`, `None` if the bound is open
+#[derive(Debug, Clone)]
+pub(super) struct VariantRange {
+    pub start: Option<TokenStream>,
+    pub end: Option<TokenStream>,
+    pub inclusive: bool,
+}
 
 #[derive(Debug, Clone)]
 pub(super) struct NetEnumVariants {
     pub ident: proc_macro2::Ident,
     pub discriminant: Option<proc_macro2::TokenStream>,
+    /// the variant holds the value, which is any value of the range
+    pub range: Option<VariantRange>,
+    /// the other values that are deserialized into the variant
+    pub aliases: Vec<TokenStream>,
+    /// the number of fields of the variant
+    pub fields: usize,
 }
 
 impl From<&syn::Variant> for NetEnumVariants {
     fn from(value: &syn::Variant) -> Self {
-        Self {
+        let mut v = Self {
             ident: value.ident.clone(),
             discriminant: value.discriminant.clone().map(|(_, e)| e.to_token_stream()),
+            range: None,
+            aliases: Vec::new(),
+            fields: value.fields.len(),
+        };
+        parse_attr(&value.attrs, ATTR_PATH, |tokens| {
+            for arg in split_attr_args(tokens) {
+                let mut it = arg.into_iter();
+                let expect_msg = format!(
+                    "Expected \"{} = <VALUE>\", \"{} = <RANGE>\" or \"{} = <VALUE>, ..\" for the variant \"{}\"",
+                    VALUE_ATTR_PATH, RANGE_ATTR_PATH, ALIAS_ATTR_PATH, v.ident
+                );
+                let path = expect_ident(&mut it, expect_msg.as_str());
+                expect_punct(&mut it, '=', expect_msg.as_str());
+                match path.as_str() {
+                    RANGE_ATTR_PATH => {
+                        assert!(v.range.is_none(), "The range of the variant \"{}\" is declared twice", v.ident);
+                        v.range = Some(VariantRange::parse(it.collect(), &v.ident));
+                    }
+                    VALUE_ATTR_PATH => {
+                        assert!(
+                            v.discriminant.is_none(),
+                            "The variant \"{}\" has both a discriminant and a value",
+                            v.ident
+                        );
+                        v.discriminant = Some(byte_str_to_array(it.collect()));
+                    }
+                    ALIAS_ATTR_PATH => v.aliases.extend(split_values(it.collect()).into_iter().map(byte_str_to_array)),
+                    _ => panic!("{}", expect_msg),
+                }
+            }
+        });
+        v
+    }
+}
+
+impl NetEnumVariants {
+    /// the values that are deserialized into the variant, as inclusive bounds
+    pub(super) fn bounds(&self, ty: &TokenStream) -> Vec<(TokenStream, TokenStream)> {
+        let mut bounds: Vec<(TokenStream, TokenStream)> = Vec::new();
+        if let Some(d) = &self.discriminant {
+            bounds.push((d.clone(), d.clone()));
+        }
+        bounds.extend(self.aliases.iter().map(|a| (a.clone(), a.clone())));
+        if let Some(r) = &self.range {
+            let start = match &r.start {
+                Some(s) => s.clone(),
+                None => quote!(<#ty>::MIN),
+            };
+            let end = match (&r.end, r.inclusive) {
+                (Some(e), true) => e.clone(),
+                (Some(e), false) => quote!((#e) - 1),
+                (None, _) => quote!(<#ty>::MAX),
+            };
+            bounds.push((start, end));
+        }
+        bounds
+    }
+}
+
+impl VariantRange {
+    /// splits `<START>..=<END>` or `<START>..<END>` at the top level `..`
+    fn parse(ts: TokenStream, ident: &proc_macro2::Ident) -> Self {
+        let tokens: Vec<TokenTree> = ts.into_iter().collect();
+        let is_dot = |i: usize| matches!(tokens.get(i), Some(TokenTree::Punct(p)) if p.as_char() == '.');
+        let Some(pos) = (0..tokens.len()).find(|i| {
+            is_dot(*i) && is_dot(i + 1) && matches!(&tokens[*i], TokenTree::Punct(p) if p.spacing() == Spacing::Joint)
+        }) else {
+            panic!("Expected a range \"<START>..=<END>\" for the variant \"{}\"", ident);
+        };
+        let inclusive = matches!(tokens.get(pos + 2), Some(TokenTree::Punct(p)) if p.as_char() == '=');
+        let end_pos = pos + if inclusive { 3 } else { 2 };
+        let bound = |t: &[TokenTree]| match t.is_empty() {
+            true => None,
+            false => Some(TokenStream::from_iter(t.iter().cloned())),
+        };
+        let range = Self {
+            start: bound(&tokens[..pos]),
+            end: bound(&tokens[end_pos..]),
+            inclusive,
+        };
+        assert!(
+            !inclusive || range.end.is_some(),
+            "Expected the end of the inclusive range of the variant \"{}\"",
+            ident
+        );
+        range
+    }
+
+    /// the condition that `v` is in the range, usable in a `const fn`
+    pub(super) fn contains(&self, v: &TokenStream) -> TokenStream {
+        let start = self.start.as_ref().map(|s| quote!(#v >= #s));
+        let end = self.end.as_ref().map(|e| match self.inclusive {
+            true => quote!(#v <= #e),
+            false => quote!(#v < #e),
+        });
+        match (start, end) {
+            (Some(s), Some(e)) => quote!((#s && #e)),
+            (Some(c), None) | (None, Some(c)) => c,
+            (None, None) => quote!(true),
+        }
+    }
+}
+
+/// a byte string literal, e.g. `b"RIFF"`, is turned into an array literal, which is both a pattern and
+/// a value of the type `[u8; N]`
+fn byte_str_to_array(ts: TokenStream) -> TokenStream {
+    match syn::parse2::<syn::LitByteStr>(ts.clone()) {
+        Ok(lit) => {
+            let bytes = lit.value();
+            quote!([#(#bytes),*])
+        }
+        Err(_) => ts,
+    }
+}
+
+/// splits `<VALUE>, <VALUE>, ..` at the top level commas
+fn split_values(ts: TokenStream) -> Vec<TokenStream> {
+    let mut values = vec![TokenStream::new()];
+    for tt in ts {
+        match &tt {
+            TokenTree::Punct(p) if p.as_char() == ',' => values.push(TokenStream::new()),
+            _ => values.last_mut().unwrap().extend([tt]),
         }
     }
+    values.into_iter().filter(|v| !v.is_empty()).collect()
 }
@@ -0,0 +1,342 @@
+use std::str::FromStr;
+
+use crate::{err::DeriveErr, helper::*};
+use proc_macro2::*;
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Fields};
+
+const ATTR_PATH: &'static str = "net_flags";
+const STRICT_ATTR_PATH: &'static str = "strict";
+
+#[derive(Clone)]
+pub(super) struct NetFlags {
+    derive_input: DeriveInput,
+    /// the integer type of the only field of the tuple structure
+    repr: TokenStream,
+    /// the names and values of the flags
+    flags: Vec<(Ident, TokenStream)>,
+    /// unknown bits are rejected when deserializing
+    strict: bool,
+}
+
+impl From<DeriveInput> for NetFlags {
+    fn from(di: DeriveInput) -> Self {
+        let Data::Struct(ds) = &di.data else {
+            panic!("Expected a structure");
+        };
+        let repr = match &ds.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => fields.unnamed[0].ty.to_token_stream(),
+            _ => panic!(
+                "Expected a tuple structure with an integer as the only field, e.g. \"struct {}(u8);\"",
+                di.ident
+            ),
+        };
+        let mut nf = Self {
+            derive_input: di.clone(),
+            repr,
+            flags: Vec::new(),
+            strict: false,
+        };
+        parse_attr(&di.attrs, ATTR_PATH, |tokens| {
+            nf.parse_attr_flags(tokens);
+        });
+        nf
+    }
+}
+
+impl NetFlags {
+    pub fn derive_input_to_token_stream(di: DeriveInput) -> Result<TokenStream, DeriveErr> {
+        Self::from(di).into()
+    }
+
+    /// parses `<FLAG> = <VALUE>` and `strict`
+    fn parse_attr_flags(&mut self, ts: &TokenStream) {
+        for arg in split_attr_args(ts) {
+            let mut it = arg.into_iter().peekable();
+            let expect_ident_msg = format!("Expected a flag \"<NAME> = <VALUE>\" or \"{}\"", STRICT_ATTR_PATH);
+            let ident = match it.next() {
+                Some(TokenTree::Ident(i)) => i,
+                _ => panic!("{}", expect_ident_msg),
+            };
+            if it.peek().is_none() {
+                assert_eq!(ident.to_string(), STRICT_ATTR_PATH, "{}", expect_ident_msg);
+                self.strict = true;
+                continue;
+            }
+            expect_punct(&mut it, '=', expect_ident_msg.as_str());
+            let value = TokenStream::from_iter(it);
+            assert!(!value.is_empty(), "Expected a value for the flag \"{}\"", ident);
+            assert!(
+                self.flags.iter().all(|(i, _)| *i != ident),
+                "The flag \"{}\" is declared twice",
+                ident
+            );
+            self.flags.push((ident, value));
+        }
+    }
+
+    fn impl_flags(&self) -> TokenStream {
+        let name = &self.derive_input.ident;
+        let name_str = name.to_string();
+        let ty = &self.repr;
+        let idents = self.flags.iter().map(|(i, _)| i).collect::<Vec<_>>();
+        let names = idents.iter().map(|i| i.to_string());
+        let values = self.flags.iter().map(|(_, v)| v).collect::<Vec<_>>();
+        quote! {
+            impl #name {
+                #(pub const #idents: Self = Self(#values);)*
+
+                /// all the named flags
+                pub const ALL: Self = Self(0 #(| #values)*);
+
+                const NAMES: &'static [(&'static str, #ty)] = &[#((#names, #values)),*];
+
+                #[inline]
+                pub const fn empty() -> Self {
+                    Self(0)
+                }
+
+                #[inline]
+                pub const fn all() -> Self {
+                    Self::ALL
+                }
+
+                #[inline]
+                pub const fn bits(&self) -> #ty {
+                    self.0
+                }
+
+                /// fails with `SerdeErr::InvalidValue` if there are bits that are not a named flag
+                #[inline]
+                pub const fn from_bits(bits: #ty) -> Result<Self, net_struct_serde::SerdeErr> {
+                    match bits & !Self::ALL.0 {
+                        0 => Ok(Self(bits)),
+                        _ => Err(net_struct_serde::SerdeErr::InvalidValue(#name_str)),
+                    }
+                }
+
+                /// the bits that are not a named flag are removed
+                #[inline]
+                pub const fn from_bits_truncate(bits: #ty) -> Self {
+                    Self(bits & Self::ALL.0)
+                }
+
+                /// the bits that are not a named flag are kept
+                #[inline]
+                pub const fn from_bits_retain(bits: #ty) -> Self {
+                    Self(bits)
+                }
+
+                /// the bits that are not a named flag
+                #[inline]
+                pub const fn unknown_bits(&self) -> #ty {
+                    self.0 & !Self::ALL.0
+                }
+
+                #[inline]
+                pub const fn is_empty(&self) -> bool {
+                    self.0 == 0
+                }
+
+                #[inline]
+                pub const fn contains(&self, other: Self) -> bool {
+                    self.0 & other.0 == other.0
+                }
+
+                #[inline]
+                pub const fn intersects(&self, other: Self) -> bool {
+                    self.0 & other.0 != 0
+                }
+
+                #[inline]
+                pub const fn union(self, other: Self) -> Self {
+                    Self(self.0 | other.0)
+                }
+
+                #[inline]
+                pub const fn intersection(self, other: Self) -> Self {
+                    Self(self.0 & other.0)
+                }
+
+                #[inline]
+                pub const fn difference(self, other: Self) -> Self {
+                    Self(self.0 & !other.0)
+                }
+
+                #[inline]
+                pub const fn symmetric_difference(self, other: Self) -> Self {
+                    Self(self.0 ^ other.0)
+                }
+
+                /// the named flags that are not set
+                #[inline]
+                pub const fn complement(self) -> Self {
+                    Self(!self.0 & Self::ALL.0)
+                }
+
+                #[inline]
+                pub fn insert(&mut self, other: Self) {
+                    self.0 |= other.0;
+                }
+
+                #[inline]
+                pub fn remove(&mut self, other: Self) {
+                    self.0 &= !other.0;
+                }
+
+                #[inline]
+                pub fn toggle(&mut self, other: Self) {
+                    self.0 ^= other.0;
+                }
+
+                #[inline]
+                pub fn set(&mut self, other: Self, value: bool) {
+                    match value {
+                        true => self.insert(other),
+                        false => self.remove(other),
+                    }
+                }
+
+                /// the names and values of the named flags that are set
+                pub fn iter_names(&self) -> impl Iterator<Item = (&'static str, Self)> + '_ {
+                    Self::NAMES
+                        .iter()
+                        .filter(|(_, v)| *v != 0 && self.0 & *v == *v)
+                        .map(|(n, v)| (*n, Self(*v)))
+                }
+            }
+        }
+    }
+
+    fn impl_ops(&self) -> TokenStream {
+        let name = &self.derive_input.ident;
+        let ops = [
+            ("BitOr", "bitor", "BitOrAssign", "bitor_assign", "union"),
+            ("BitAnd", "bitand", "BitAndAssign", "bitand_assign", "intersection"),
+            ("BitXor", "bitxor", "BitXorAssign", "bitxor_assign", "symmetric_difference"),
+            ("Sub", "sub", "SubAssign", "sub_assign", "difference"),
+        ];
+        let mut ts = TokenStream::new();
+        for (op, op_fn, op_assign, op_assign_fn, method) in ops {
+            let [op, op_fn, op_assign, op_assign_fn, method] =
+                [op, op_fn, op_assign, op_assign_fn, method].map(|s| TokenStream::from_str(s).unwrap());
+            ts.extend(quote! {
+                impl core::ops::#op for #name {
+                    type Output = Self;
+                    #[inline]
+                    fn #op_fn(self, other: Self) -> Self {
+                        self.#method(other)
+                    }
+                }
+                impl core::ops::#op_assign for #name {
+                    #[inline]
+                    fn #op_assign_fn(&mut self, other: Self) {
+                        *self = self.#method(other);
+                    }
+                }
+            });
+        }
+        ts.extend(quote! {
+            impl core::ops::Not for #name {
+                type Output = Self;
+                #[inline]
+                fn not(self) -> Self {
+                    self.complement()
+                }
+            }
+        });
+        ts
+    }
+
+    fn impl_debug(&self) -> TokenStream {
+        let name = &self.derive_input.ident;
+        let name_str = name.to_string();
+        quote! {
+            impl core::fmt::Debug for #name {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, "{}(", #name_str)?;
+                    let mut rest = self.0;
+                    let mut first = true;
+                    for (n, v) in self.iter_names() {
+                        if !first {
+                            f.write_str(" | ")?;
+                        }
+                        f.write_str(n)?;
+                        rest &= !v.0;
+                        first = false;
+                    }
+                    match (rest, first) {
+                        (0, false) => (),
+                        (_, false) => write!(f, " | {:#x}", rest)?,
+                        (_, true) => write!(f, "{:#x}", rest)?,
+                    }
+                    f.write_str(")")
+                }
+            }
+        }
+    }
+
+    fn impl_serde(&self) -> TokenStream {
+        let name = &self.derive_input.ident;
+        let ty = &self.repr;
+        let from_bits = match self.strict {
+            true => quote!(Self::from_bits(bits)),
+            false => quote!(Ok(Self::from_bits_retain(bits))),
+        };
+        quote! {
+            impl net_struct_serde::traits::Serialize for #name {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                    where S: net_struct_serde::traits::Serializer
+                {
+                    self.0.serialize(serializer)
+                }
+            }
+            impl net_struct_serde::traits::Deserialize for #name {
+                fn deserialize<D>(deserializer: D) -> Result<Self, net_struct_serde::SerdeErr>
+                    where D: net_struct_serde::traits::Deserializer
+                {
+                    let bits = <#ty as net_struct_serde::traits::Deserialize>::deserialize(deserializer)?;
+                    #from_bits
+                }
+            }
+            impl net_struct_serde::traits::Placeholder for #name {
+                #[inline]
+                fn placeholder() -> Self {
+                    Self::empty()
+                }
+            }
+            impl net_struct_serde::traits::FixedLen for #name {
+                const LEN: usize = <#ty as net_struct_serde::traits::FixedLen>::LEN;
+            }
+            impl net_struct_serde::traits::NetFlags for #name {
+                type Bits = #ty;
+                #[inline]
+                fn bits(&self) -> #ty {
+                    self.0
+                }
+                #[inline]
+                fn from_bits_retain(bits: #ty) -> Self {
+                    Self(bits)
+                }
+            }
+        }
+    }
+}
+
+impl Into<Result<TokenStream, DeriveErr>> for NetFlags {
+    fn into(self) -> Result<TokenStream, DeriveErr> {
+        if self.flags.is_empty() {
+            return Err(DeriveErr::Message(format!(
+                "Expected at least one flag \"#[{}(<NAME> = <VALUE>, ..)]\" for \"{}\"",
+                ATTR_PATH,
+                self.derive_input.ident
+            )));
+        }
+        let mut ts = TokenStream::new();
+        ts.extend(self.impl_flags());
+        ts.extend(self.impl_ops());
+        ts.extend(self.impl_debug());
+        ts.extend(self.impl_serde());
+        Ok(ts)
+    }
+}
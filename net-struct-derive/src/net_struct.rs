@@ -1,16 +1,25 @@
 mod cmp;
 mod field;
 mod parser;
+mod placeholder;
 mod serializer;
+mod view;
+mod zerocopy;
 use crate::{err::DeriveErr, helper::*};
-use field::{FieldAttr, NetStructField, SizeUnit, VecField};
-use proc_macro2::{Delimiter, TokenStream};
+use field::{
+    BaseUnit, FieldAttr, LenOfGroup, NetStructField, NetStructFieldType, SizeUnit, VecField, LEN_OF_SELF,
+    PAD_TO_ATTR_PATH,
+};
+use proc_macro2::TokenStream;
 use quote::quote;
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, rc::Rc, str::FromStr};
 use syn::{Data, DeriveInput};
 
 const ATTR_PATH: &'static str = "net_struct";
 const STRUCT_SIZE_PATH: &'static str = "struct_len";
+const VERSION_FIELD_PATH: &'static str = "version_field";
+const VIEW_PATH: &'static str = "view";
+const ZEROCOPY_PATH: &'static str = "zerocopy";
 
 #[derive(Clone)]
 pub(super) struct NetStruct {
@@ -22,6 +31,12 @@ pub(super) struct NetStruct {
 #[derive(Clone)]
 struct NetStructAttr {
     struct_len: Option<(Rc<NetStructField>, SizeUnit)>,
+    pad_to: Option<String>,
+    version_field: Option<Rc<NetStructField>>,
+    /// the structure is `traits::ZeroCopy`
+    zerocopy: bool,
+    /// the view of the structure is derived, e.g. `FooView`
+    view: bool,
 }
 
 impl std::cmp::PartialEq for NetStruct {
@@ -33,7 +48,7 @@ impl std::cmp::Eq for NetStruct {}
 
 impl NetStruct {
     pub fn derive_input_to_token_stream(di: DeriveInput) -> Result<TokenStream, DeriveErr> {
-        Self::from(di).into()
+        Self::try_from(di)?.into()
     }
 
     fn find_field_from_name(&self, name: String) -> Option<Rc<NetStructField>> {
@@ -69,32 +84,310 @@ impl NetStruct {
         ))
     }
 
-    fn parse_attr_struct_len(&mut self, ts: &TokenStream) -> String {
-        let expect_group_msg = format!(
-            "Expected parenthesis with arguments after \"{}\"",
-            STRUCT_SIZE_PATH
-        );
-        let expect_attr_name_msg = format!("Expected identifier \"{}\"", STRUCT_SIZE_PATH);
-        let expect_field_name_msg = format!("Expected a field name for \"{}\"", STRUCT_SIZE_PATH);
-        const NO_SUCH_FIELD_MSG: &'static str = "specified field for struct_len is not found";
+    /// the groups of fields declared by `len_of`, `self` stands for all the fields of the struct
+    fn find_all_len_of_groups(&self) -> Vec<LenOfGroup> {
+        self.fields
+            .iter()
+            .flat_map(|len_field| {
+                len_field.len_of().into_iter().map(|(names, unit)| LenOfGroup {
+                    len_field: len_field.clone(),
+                    fields: match names.iter().any(|n| n == LEN_OF_SELF) {
+                        true => self.fields.clone(),
+                        false => names
+                            .iter()
+                            .filter_map(|n| self.find_field_from_name(n.clone()))
+                            .collect(),
+                    },
+                    unit: unit.clone(),
+                })
+            })
+            .collect()
+    }
 
-        let mut it = ts.clone().into_iter();
-        assert_eq!(
-            expect_ident(&mut it, expect_attr_name_msg.as_str()),
-            String::from(STRUCT_SIZE_PATH),
-            "{}",
-            expect_attr_name_msg.as_str(),
-        );
-        let mut arg_it = expect_group(&mut it, Delimiter::Parenthesis, expect_group_msg.as_str())
+    /// the group whose length is held by the length field of the vector `v_f`, the vector then takes
+    /// the rest of the group
+    fn find_len_of_group(&self, v_f: &VecField) -> Option<LenOfGroup> {
+        self.find_all_len_of_groups()
             .into_iter()
-            .peekable();
-        let struct_len_field_name = expect_ident(&mut arg_it, expect_field_name_msg.as_str());
-        consume_punct(&mut arg_it, ',');
+            .find(|g| g.len_field.name == v_f.len_field.name)
+    }
+
+    /// the number of elements of the vector field `f` of `self` as a `Result<usize, SerdeErr>`, counted by
+    /// their encoded sizes if the length is in bytes or bits, `group_consumed` is the number of bytes of
+    /// the `len_of` group before the vector if it takes the rest of the group
+    fn vec_count(
+        &self,
+        f: &Rc<NetStructField>,
+        v_f: &VecField,
+        group_consumed: impl FnOnce(&LenOfGroup) -> TokenStream,
+    ) -> TokenStream {
+        let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+        let len_field = TokenStream::from_str(v_f.len_field.name.as_str()).unwrap();
+        if let Some(group) = self.find_len_of_group(v_f) {
+            let group_len = group.to_bytes(quote!(self.#len_field));
+            let consumed = group_consumed(&group);
+            return quote! {
+                (#group_len)
+                    .and_then(|l| l.checked_sub(#consumed).ok_or(net_struct_serde::SerdeErr::OutOfRange))
+                    .and_then(|bytes| net_struct_serde::encoded_count(&self.#field_name, bytes))
+            };
+        }
+        match v_f.len_unit.is_count() {
+            true => v_f.len_unit.to_count(&v_f.len_field.name, quote!(self.#len_field)),
+            false => {
+                let bytes = v_f.len_unit.to_bytes(&v_f.len_field.name, quote!(self.#len_field));
+                quote!((#bytes).and_then(|bytes| net_struct_serde::encoded_count(&self.#field_name, bytes)))
+            }
+        }
+    }
+
+    /// whether the field `f` is present in the version held by the `version_field`
+    fn version_gate(&self, f: &Rc<NetStructField>) -> Option<TokenStream> {
+        let (since, until) = f.version_range()?;
+        let version = TokenStream::from_str(self.attrs.version_field.as_ref()?.name.as_str()).unwrap();
+        let since = since.map(|since| quote!(#version >= (#since)));
+        let until = until.map(|until| quote!(#version <= (#until)));
+        Some(match (since, until) {
+            (Some(since), Some(until)) => quote!((#since && #until)),
+            (Some(gate), None) | (None, Some(gate)) => quote!((#gate)),
+            (None, None) => unreachable!(),
+        })
+    }
+
+    /// checks that the versioned fields are in a structure with a `version_field`
+    fn check_version_fields(&self) -> Result<(), DeriveErr> {
+        let struct_name = self.derive_input.ident.to_string();
+        match (&self.attrs.version_field, self.fields.iter().find(|f| f.version_range().is_some())) {
+            (None, Some(f)) => Err(DeriveErr::Message(format!(
+                "The versioned field \"{}\" is in the structure \"{}\" without a version_field",
+                &f.name, struct_name))),
+            (Some(v), _) if v.version_range().is_some() => Err(DeriveErr::Message(format!(
+                "The version_field \"{}\" of the structure \"{}\" cannot be versioned",
+                &v.name, struct_name))),
+            _ => Ok(()),
+        }
+    }
+
+    /// the fields referred to by the condition of `f`, which must be deserialized before `f`
+    fn condition_deps(&self, f: &Rc<NetStructField>) -> Vec<String> {
+        fn idents(ts: TokenStream, out: &mut Vec<String>) {
+            for tt in ts {
+                match tt {
+                    proc_macro2::TokenTree::Ident(i) => out.push(i.to_string()),
+                    proc_macro2::TokenTree::Group(g) => idents(g.stream(), out),
+                    _ => (),
+                }
+            }
+        }
+        let mut names = Vec::new();
+        if let Some(condition) = f.condition() {
+            idents(condition, &mut names);
+        }
+        if let Some(gate) = self.version_gate(f) {
+            idents(gate, &mut names);
+        }
+        self.fields
+            .iter()
+            .filter(|other| other.name != f.name && names.contains(&other.name))
+            .map(|other| other.name.clone())
+            .collect()
+    }
+
+    /// binds the fields referred to by the condition or the version gate of `f` to their names
+    fn bind_condition_deps(&self, f: &Rc<NetStructField>) -> TokenStream {
+        let deps = self.condition_deps(f).into_iter().map(|d| TokenStream::from_str(d.as_str()).unwrap());
+        quote!(#(let #deps = self.#deps.clone();)*)
+    }
+
+    /// whether the offset from the start of the struct is needed for the padding
+    fn is_padded(&self) -> bool {
+        self.attrs.pad_to.is_some() || self.fields.iter().any(|f| f.pad_to().is_some())
+    }
+
+    /// moves the length relationships declared by `field_vec_len` on the length fields onto the vector fields
+    fn resolve_field_vec_len(fields: &mut [NetStructField], struct_name: &str) -> Result<(), DeriveErr> {
+        let len_of: Vec<(String, String, SizeUnit)> = fields
+            .iter()
+            .flat_map(|f| {
+                f.vec_len_of()
+                    .into_iter()
+                    .map(|(vec_field, unit)| (f.name.clone(), vec_field, unit))
+            })
+            .collect();
+        for (len_field, vec_field, unit) in len_of {
+            let Some(f) = fields.iter_mut().find(|f| f.name == vec_field) else {
+                return Err(DeriveErr::Message(format!(
+                    "The vector field \"{}\" given by field_vec_len on \"{}\" is not found in the structure \"{}\"",
+                    vec_field, len_field, struct_name)));
+            };
+            f.net_struct_attr.push(FieldAttr::Vec {
+                vec_len_field: len_field,
+                unit,
+            });
+        }
+        Ok(())
+    }
+
+    /// whether the structure has a `version_field`, known before the fields are resolved
+    fn has_version_field(di: &DeriveInput) -> bool {
+        let mut found = false;
+        parse_attr(&di.attrs, ATTR_PATH, |tokens| {
+            found |= split_attr_args(tokens).into_iter().any(|arg| {
+                matches!(arg.into_iter().next(), Some(proc_macro2::TokenTree::Ident(i)) if i == VERSION_FIELD_PATH)
+            });
+        });
+        found
+    }
+
+    /// `until` is the last version of a field in a structure with a `version_field`, and the terminator of an
+    /// array otherwise or with `terminator(..)`, an array in a structure with a `version_field` must say which
+    fn resolve_until(fields: &mut [NetStructField], struct_name: &str, versioned: bool) -> Result<(), DeriveErr> {
+        for f in fields.iter_mut() {
+            let Some(i) = f.net_struct_attr.iter().position(|attr| matches!(attr, FieldAttr::Until(_))) else {
+                continue;
+            };
+            let is_array = matches!(f.ty, NetStructFieldType::Arr { .. });
+            let is_terminated = f.net_struct_attr.iter().any(|attr| matches!(attr, FieldAttr::Terminator(_)));
+            match (versioned, is_terminated, is_array) {
+                (true, false, true) => {
+                    return Err(DeriveErr::Message(format!(
+                        "The until of the array field \"{}\" of the structure \"{}\" with a version_field is ambiguous, write until_version = <VERSION> for its last version or add terminator(..) for its terminator",
+                        &f.name, struct_name)));
+                }
+                (true, false, false) => {
+                    let FieldAttr::Until(expr) = f.net_struct_attr.remove(i) else {
+                        unreachable!();
+                    };
+                    f.net_struct_attr.push(FieldAttr::UntilVersion(expr));
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
 
-        let len_unit = consume_ident(&mut arg_it)
-            .map(|s| SizeUnit::from(s))
-            .unwrap_or(SizeUnit::BYTES);
+    /// checks that the length of each vector field is declared exactly once, by an existing field
+    fn check_vec_fields(&self) -> Result<(), DeriveErr> {
+        let struct_name = self.derive_input.ident.to_string();
+        for f in self.fields.iter() {
+            let lens = f.vec_lens();
+            if lens.len() > 1 {
+                let what = match lens.iter().all(|l| *l == lens[0]) {
+                    true => "declared twice",
+                    false => "declared inconsistently",
+                };
+                return Err(DeriveErr::Message(format!(
+                    "The length of the vector field \"{}\" of the structure \"{}\" is {} (by vec_len or field_vec_len)",
+                    &f.name, struct_name, what)));
+            }
+            let Some((len_field, _)) = lens.first() else {
+                continue;
+            };
+            if self.find_field_from_name((*len_field).clone()).is_none() {
+                return Err(DeriveErr::Message(format!(
+                    "The length field \"{}\" of the vector field \"{}\" of the structure \"{}\" is not found",
+                    len_field, &f.name, struct_name)));
+            }
+            if !f.is_vec_type() {
+                return Err(DeriveErr::Message(format!(
+                    "The vector field \"{}\" of the structure \"{}\" is not an array",
+                    &f.name, struct_name)));
+            }
+        }
+        Ok(())
+    }
 
+    /// checks that each `len_of` group is made of existing consecutive fields, and that a vector taking
+    /// the rest of a group is the last field of the group
+    fn check_len_of_groups(&self) -> Result<(), DeriveErr> {
+        let struct_name = self.derive_input.ident.to_string();
+        let err = |msg: String| Err(DeriveErr::Message(format!("{} in the structure \"{}\"", msg, struct_name)));
+        let all_vec = self.find_all_vec_fields();
+        for len_field in self.fields.iter() {
+            let groups = len_field.len_of();
+            if groups.len() > 1 {
+                return err(format!("The group length of the field \"{}\" is declared twice", &len_field.name));
+            }
+            let Some((names, _)) = groups.first() else {
+                continue;
+            };
+            if len_field.is_phantom() || len_field.is_vec() {
+                return err(format!("The group length field \"{}\" cannot be a phantom field nor a vector", &len_field.name));
+            }
+            let is_self = names.iter().any(|n| n == LEN_OF_SELF);
+            if is_self && names.len() > 1 {
+                return err(format!("The group of \"{}\" cannot be both \"self\" and a list of fields", &len_field.name));
+            }
+            let mut indices = Vec::new();
+            for name in names.iter().filter(|n| !is_self || *n != LEN_OF_SELF) {
+                let Some(i) = self.fields.iter().position(|f| &f.name == name) else {
+                    return err(format!("The field \"{}\" in the group of \"{}\" is not found", name, &len_field.name));
+                };
+                indices.push(i);
+            }
+            if indices.windows(2).any(|w| w[1] != w[0] + 1) {
+                return err(format!("The fields in the group of \"{}\" are not consecutive", &len_field.name));
+            }
+        }
+        for group in self.find_all_len_of_groups() {
+            if let Some(f) = group.fields.iter().find(|f| f.pad_to().is_some()) {
+                return err(format!("The padded field \"{}\" cannot be in the group of \"{}\"", &f.name, &group.len_field.name));
+            }
+            for (name, v_f) in all_vec.iter().filter(|(_, v_f)| v_f.len_field.name == group.len_field.name) {
+                let Some(f) = self.find_field_from_name(name.clone()) else {
+                    continue;
+                };
+                if !group.is_last(&f) {
+                    return err(format!("The vector field \"{}\" taking the rest of the group of \"{}\" is not the last field of the group", name, &group.len_field.name));
+                }
+                if v_f.len_unit != SizeUnit::default() {
+                    return err(format!("The vector field \"{}\" taking the rest of the group of \"{}\" cannot have a length unit", name, &group.len_field.name));
+                }
+                if let Some(f) = group.fields.iter().find(|f| f.is_vec() && self.find_len_of_group(&all_vec[&f.name]).is_some() && f.name != *name) {
+                    return err(format!("The vector field \"{}\" taking the rest of another group cannot be in the group of \"{}\"", &f.name, &group.len_field.name));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_attrs(&mut self, ts: &TokenStream) {
+        let expect_attr_name_msg = format!("Expected a struct attribute for \"{}\"", ATTR_PATH);
+        for arg in split_attr_args(ts) {
+            let mut it = arg.into_iter().peekable();
+            match expect_ident(&mut it, expect_attr_name_msg.as_str()).as_str() {
+                STRUCT_SIZE_PATH => {
+                    self.parse_attr_struct_len(&mut it);
+                }
+                VERSION_FIELD_PATH => {
+                    let name = NetStructField::parse_attr_value(&mut it, VERSION_FIELD_PATH);
+                    let field = self
+                        .find_field_from_name(name)
+                        .expect("specified field for version_field is not found");
+                    self.attrs.version_field = Some(field);
+                }
+                PAD_TO_ATTR_PATH => {
+                    self.attrs.pad_to = Some(parse_attr_group_arg(&mut it, PAD_TO_ATTR_PATH));
+                }
+                ZEROCOPY_PATH => {
+                    self.attrs.zerocopy = true;
+                }
+                VIEW_PATH => {
+                    self.attrs.view = true;
+                }
+                _ => (),
+            }
+        }
+    }
+
+    fn parse_attr_struct_len<I>(&mut self, it: &mut I) -> String
+    where
+        I: Iterator<Item = proc_macro2::TokenTree>,
+    {
+        const NO_SUCH_FIELD_MSG: &'static str = "specified field for struct_len is not found";
+
+        let (struct_len_field_name, len_unit) =
+            NetStructField::parse_attr_vec_len(it, STRUCT_SIZE_PATH, BaseUnit::BYTES);
         let net_struct_len_field = self
             .fields
             .iter()
@@ -105,34 +398,55 @@ impl NetStruct {
     }
 }
 
-impl From<DeriveInput> for NetStruct {
-    fn from(di: DeriveInput) -> Self {
+impl TryFrom<DeriveInput> for NetStruct {
+    type Error = DeriveErr;
+
+    fn try_from(di: DeriveInput) -> Result<Self, DeriveErr> {
         let Data::Struct(ds) = &di.data else {
             panic!("Expected a struct");
         };
+        let mut fields: Vec<NetStructField> = ds.fields.iter().map(NetStructField::from).collect();
+        let struct_name = di.ident.to_string();
+        Self::resolve_field_vec_len(&mut fields, &struct_name)?;
+        Self::resolve_until(&mut fields, &struct_name, Self::has_version_field(&di))?;
         let mut ns = Self {
             derive_input: di.clone(),
-            fields: ds
-                .fields
-                .iter()
-                .map(|f| Rc::new(NetStructField::from(f)))
+            fields: fields
+                .into_iter()
+                .map(|mut f| {
+                    f.update_vec_type();
+                    Rc::new(f)
+                })
                 .collect(),
-            attrs: NetStructAttr { struct_len: None },
+            attrs: NetStructAttr {
+                struct_len: None,
+                pad_to: None,
+                version_field: None,
+                zerocopy: false,
+                view: false,
+            },
         };
         parse_attr(&di.attrs, ATTR_PATH, |tokens| {
-            ns.parse_attr_struct_len(tokens);
+            ns.parse_attrs(tokens);
         });
-        ns
+        Ok(ns)
     }
 }
 
 impl Into<Result<TokenStream, DeriveErr>> for NetStruct {
     fn into(self) -> Result<TokenStream, DeriveErr> {
         let struct_name = &self.derive_input.ident;
+        self.check_vec_fields()?;
+        self.check_len_of_groups()?;
+        self.check_version_fields()?;
         let mut ts = TokenStream::new();
         ts.extend(self.serializer()?);
         ts.extend(self.parser()?);
         ts.extend(self.comparer()?);
+        ts.extend(self.placeholder()?);
+        ts.extend(self.zerocopy()?);
+        ts.extend(self.view()?);
+        ts.extend(self.fixed_len()?);
         ts.extend(quote! {impl net_struct_serde::traits::NetStruct for #struct_name {} });
         Ok(ts)
     }
@@ -1,13 +1,113 @@
 use std::str::FromStr;
 
 use super::*;
-use field::NetStructFieldType;
+use field::{NetStructFieldType, OptionMode, TerminatorMode};
 use proc_macro2::TokenStream;
 use quote::quote;
 
 const ACC_VAR_NAME: &'static str = "is_same";
 
 impl NetStruct {
+    /// the number of bytes that the field of `self` is serialized into
+    fn encoded_size(&self, f: &Rc<NetStructField>, all_vec: &HashMap<String, VecField>) -> TokenStream {
+        let size = self.encoded_size_in_version(f, all_vec);
+        match self.version_gate(f) {
+            Some(gate) => {
+                let deps = self.bind_condition_deps(f);
+                quote!({
+                    #deps
+                    match #gate {
+                        true => #size,
+                        false => 0usize,
+                    }
+                })
+            }
+            None => size,
+        }
+    }
+
+    /// the number of bytes that the field of `self` is serialized into, if it is present in the version
+    fn encoded_size_in_version(&self, f: &Rc<NetStructField>, all_vec: &HashMap<String, VecField>) -> TokenStream {
+        let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+        if f.is_phantom() {
+            return quote!(0usize);
+        }
+        if let Some(len) = f.reserved() {
+            return quote!((#len as usize));
+        }
+        if let Some(expr) = f.const_value() {
+            return quote!(net_struct_serde::encoded_len(&(#expr)).unwrap_or(0));
+        }
+        if let Some((encode, _)) = f.flavour() {
+            return quote! {
+                #encode(&self.#field_name)
+                    .ok()
+                    .and_then(|wire| net_struct_serde::encoded_len(&wire).ok())
+                    .unwrap_or(0)
+            };
+        }
+        if let (Some(mode), Some(ty)) = (f.option_mode(), f.option_inner_ty()) {
+            let value_len = quote! {
+                self.#field_name.as_ref().map(|v| net_struct_serde::encoded_len(v).unwrap_or(0)).unwrap_or(0)
+            };
+            return match mode {
+                OptionMode::Presence => quote!((1usize + #value_len)),
+                OptionMode::Sentinel(sentinel) => {
+                    let sentinel = TokenStream::from_str(sentinel.as_str()).unwrap();
+                    quote! {
+                        match &self.#field_name {
+                            Some(v) => net_struct_serde::encoded_len(v).unwrap_or(0),
+                            None => {
+                                let sentinel: #ty = #sentinel;
+                                net_struct_serde::encoded_len(&sentinel).unwrap_or(0)
+                            }
+                        }
+                    }
+                }
+                OptionMode::LenPrefix(len_ty) => {
+                    let len_ty = TokenStream::from_str(len_ty.as_str()).unwrap();
+                    quote!({
+                        let len = #value_len;
+                        <#len_ty as net_struct_serde::traits::LenField>::from_len(len)
+                            .ok()
+                            .and_then(|l| net_struct_serde::encoded_len(&l).ok())
+                            .unwrap_or(0)
+                            + len
+                    })
+                }
+                OptionMode::Trailing => value_len,
+            };
+        }
+        if let (Some((terminator, mode)), NetStructFieldType::Arr { ty, .. }) = (f.until(), &f.ty) {
+            let terminator_len = match mode {
+                TerminatorMode::Peek => quote!(0usize),
+                _ => quote!(net_struct_serde::encoded_len(&terminator).unwrap_or(0)),
+            };
+            return quote!({
+                let terminator: #ty = #terminator;
+                self.#field_name
+                    .iter()
+                    .take_while(|e| **e != terminator)
+                    .map(|e| net_struct_serde::encoded_len(e).unwrap_or(0))
+                    .sum::<usize>()
+                    + #terminator_len
+            });
+        }
+        match (&f.ty, all_vec.get(&f.name)) {
+            (NetStructFieldType::Vec { .. }, Some(v_f)) => {
+                // a vector taking the rest of a group is never before the vector of another group
+                let count = self.vec_count(f, v_f, |_| quote!(0usize));
+                quote! {
+                    self.#field_name[0..(#count).unwrap_or(self.#field_name.len())]
+                        .iter()
+                        .map(|e| net_struct_serde::encoded_len(e).unwrap_or(0))
+                        .sum::<usize>()
+                }
+            }
+            _ => quote!(net_struct_serde::encoded_len(&self.#field_name).unwrap_or(0)),
+        }
+    }
+
     /**
      * writes the implements of Eq for the NetStruct
      */
@@ -15,33 +115,57 @@ impl NetStruct {
         let mut fields_serialize_ts = TokenStream::new();
         let all_vec = self.find_all_vec_fields();
         let var = TokenStream::from_str(ACC_VAR_NAME).unwrap();
-        for f in self.fields.iter().filter(|f| !f.is_phantom()) {
+        for f in self
+            .fields
+            .iter()
+            .filter(|f| !f.is_phantom() && f.const_value().is_none() && f.reserved().is_none())
+        {
             let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
-            fields_serialize_ts.extend(match &f.ty {
+            let field_ts = match &f.ty {
                 NetStructFieldType::Val { ty: _, } => {
                     quote! { #var &= self.#field_name == other.#field_name; }
                 },
-                NetStructFieldType::Arr { ty: _, capacity: _, } => {
-                    quote! { #var &= self.#field_name == other.#field_name; }
+                NetStructFieldType::Arr { ty, capacity: _, } => match f.until() {
+                    Some((terminator, _)) => quote! {
+                        #var &= {
+                            let terminator: #ty = #terminator;
+                            let len = |arr: &[#ty]| arr.iter().position(|e| *e == terminator).unwrap_or(arr.len());
+                            self.#field_name[..len(&self.#field_name)] == other.#field_name[..len(&other.#field_name)]
+                        };
+                    },
+                    None => quote! { #var &= self.#field_name == other.#field_name; },
                 },
-                NetStructFieldType::Vec { ty, capacity: _, } => {
+                NetStructFieldType::Vec { .. } => {
                     let Some(v_f) = all_vec.get(&f.name) else {
                         return Err(DeriveErr::Message(format!(
                             "Unexpected error when implementing core::cmp::Eq for the vector field \"{}\" of the structure \"{}\"", 
                             &f.name,
                             self.derive_input.ident.to_string())));
                     };
-                    let len_field = TokenStream::from_str(v_f.len_field.name.as_str()).unwrap();
-                    let unit = match v_f.len_unit {
-                        SizeUnit::BITS => quote!(as usize / (8_usize * core::mem::size_of::<#ty>())),
-                        SizeUnit::BYTES => quote!(as usize / core::mem::size_of::<#ty>()),
-                        SizeUnit::LENGTH => quote!(as usize),
-                    };
+                    let count = self.vec_count(f, v_f, |group| {
+                        let fixed = group.fields_before(f).iter().map(|f| self.encoded_size(f, &all_vec));
+                        quote!((0usize #(+ #fixed)*))
+                    });
                     quote! {
-                        #var &= (0..(self.#len_field #unit))
+                        #var &= (0..(#count).unwrap_or(self.#field_name.len()))
                             .fold(true, |acc, i| acc && self.#field_name[i] == other.#field_name[i]);
                     }
                 },
+            };
+            // the field is not compared if it is not present in the version
+            fields_serialize_ts.extend(match self.version_gate(f) {
+                Some(gate) => {
+                    let deps = self.bind_condition_deps(f);
+                    quote! {
+                        {
+                            #deps
+                            if #gate {
+                                #field_ts
+                            }
+                        }
+                    }
+                }
+                None => field_ts,
             });
         }
         let struct_name = &self.derive_input.ident;
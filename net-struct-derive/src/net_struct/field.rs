@@ -2,25 +2,49 @@ use super::*;
 use proc_macro2::Delimiter;
 use quote::ToTokens;
 use std::collections::BTreeMap;
+use std::str::FromStr;
 
 const VEC_LEN_ATTR_PATH: &'static str = "vec_len";
+const FIELD_VEC_LEN_ATTR_PATH: &'static str = "field_vec_len";
+const LEN_OF_ATTR_PATH: &'static str = "len_of";
+pub(super) const LEN_OF_SELF: &'static str = "self";
 const PHANTOM_ATTR_PATH: &'static str = "phantom";
+const EXPECT_ATTR_PATH: &'static str = "expect";
+const RANGE_ATTR_PATH: &'static str = "range";
+const VALIDATE_ATTR_PATH: &'static str = "validate";
+const CONST_ATTR_PATH: &'static str = "const";
+const WITH_ATTR_PATH: &'static str = "with";
+const FLAVOUR_ATTR_PATH: &'static str = "flavour";
+pub(super) const PAD_TO_ATTR_PATH: &'static str = "pad_to";
+const RESERVED_ATTR_PATH: &'static str = "reserved";
+const DEFAULT_ATTR_PATH: &'static str = "default";
+const UNTIL_ATTR_PATH: &'static str = "until";
+const TERMINATOR_ATTR_PATH: &'static str = "terminator";
+const IF_ATTR_PATH: &'static str = "if";
+const CHECK_IF_ATTR_PATH: &'static str = "check_if";
+const SINCE_ATTR_PATH: &'static str = "since";
+const UNTIL_VERSION_ATTR_PATH: &'static str = "until_version";
+const OPTION_ATTR_PATH: &'static str = "option";
 
-static SIZE_UNIT_MAP: std::sync::OnceLock<BTreeMap<String, SizeUnit>> = std::sync::OnceLock::new();
-fn size_unit_map() -> &'static BTreeMap<String, SizeUnit> {
+const WORDS_UNIT_PATH: &'static str = "words";
+const OFFSET_UNIT_PATH: &'static str = "offset";
+const EXPR_UNIT_PATH: &'static str = "expr";
+
+static SIZE_UNIT_MAP: std::sync::OnceLock<BTreeMap<String, BaseUnit>> = std::sync::OnceLock::new();
+fn size_unit_map() -> &'static BTreeMap<String, BaseUnit> {
     SIZE_UNIT_MAP.get_or_init(|| {
         BTreeMap::from([
-            (String::from("len"), SizeUnit::LENGTH),
-            (String::from("bytes"), SizeUnit::BYTES),
-            (String::from("B"), SizeUnit::BYTES),
-            (String::from("bits"), SizeUnit::BITS),
+            (String::from("len"), BaseUnit::LENGTH),
+            (String::from("bytes"), BaseUnit::BYTES),
+            (String::from("B"), BaseUnit::BYTES),
+            (String::from("bits"), BaseUnit::BITS),
         ])
     })
 }
 
 #[derive(Clone)]
 pub(super) struct NetStructField {
-    pub(super) _field: syn::Field,
+    pub(super) field: syn::Field,
     pub(super) name: String,
     pub(super) net_struct_attr: Vec<FieldAttr>,
     pub(super) ty: NetStructFieldType,
@@ -32,7 +56,66 @@ pub(super) enum FieldAttr {
         vec_len_field: String,
         unit: SizeUnit,
     },
+    /// the inverse of `Vec`, declared on the length field
+    VecLenOf {
+        vec_field: String,
+        unit: SizeUnit,
+    },
+    /// the length of a group of consecutive fields, declared on the length field
+    LenOf {
+        fields: Vec<String>,
+        unit: SizeUnit,
+    },
     Phantom,
+    Expect(String),
+    Range(String),
+    Validate(String),
+    Const(String),
+    With(String),
+    Flavour(String),
+    PadTo(String),
+    Reserved(String),
+    Default(String),
+    Until(String),
+    Terminator(TerminatorMode),
+    If(String),
+    CheckIf,
+    Since(String),
+    UntilVersion(String),
+    Option(OptionMode),
+}
+
+/// What happens to the terminator of a sequence, the variants of `traits::Terminator`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum TerminatorMode {
+    #[default]
+    Consume,
+    Include,
+    Peek,
+}
+
+impl TerminatorMode {
+    /// the path of the variant of `traits::Terminator`
+    pub(super) fn to_variant(self) -> TokenStream {
+        match self {
+            Self::Consume => quote!(net_struct_serde::traits::Terminator::Consume),
+            Self::Include => quote!(net_struct_serde::traits::Terminator::Include),
+            Self::Peek => quote!(net_struct_serde::traits::Terminator::Peek),
+        }
+    }
+}
+
+/// How an `Option` field is encoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum OptionMode {
+    /// a `u8` flag of `0` or `1` before the value
+    Presence,
+    /// `None` is encoded as the value of the expression
+    Sentinel(String),
+    /// the encoded length of the value in bytes before the value, `None` is a length of `0`
+    LenPrefix(String),
+    /// `Some` if there are bytes remaining
+    Trailing,
 }
 
 #[derive(Debug, Clone)]
@@ -43,13 +126,23 @@ pub(super) enum NetStructFieldType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub(super) enum SizeUnit {
+pub(super) enum BaseUnit {
     BITS,
     BYTES,
     #[default]
     LENGTH,
 }
 
+/// The unit of a length field: length = `wire value` * `scale` + `offset` in the base unit,
+/// or `expr` over the wire value if it is given
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(super) struct SizeUnit {
+    pub(super) base: BaseUnit,
+    pub(super) scale: Option<String>,
+    pub(super) offset: Option<String>,
+    pub(super) expr: Option<String>,
+}
+
 #[derive(Clone)]
 pub(super) struct VecField {
     pub(super) _data_field: Rc<NetStructField>,
@@ -57,15 +150,162 @@ pub(super) struct VecField {
     pub(super) len_unit: SizeUnit,
 }
 
-impl From<String> for SizeUnit {
-    fn from(s: String) -> Self {
+/// A group of consecutive fields whose length in bytes is held by `len_field`
+#[derive(Clone)]
+pub(super) struct LenOfGroup {
+    pub(super) len_field: Rc<NetStructField>,
+    pub(super) fields: Vec<Rc<NetStructField>>,
+    pub(super) unit: SizeUnit,
+}
+
+impl LenOfGroup {
+    /// the variable holding the offset of the start of the group
+    pub(super) fn start_var(&self) -> TokenStream {
+        TokenStream::from_str(format!("{}_group_start", self.len_field.name).as_str()).unwrap()
+    }
+
+    pub(super) fn contains(&self, field: &Rc<NetStructField>) -> bool {
+        self.fields.iter().any(|f| f.name == field.name)
+    }
+
+    pub(super) fn is_first(&self, field: &Rc<NetStructField>) -> bool {
+        self.fields.first().is_some_and(|f| f.name == field.name)
+    }
+
+    pub(super) fn is_last(&self, field: &Rc<NetStructField>) -> bool {
+        self.fields.last().is_some_and(|f| f.name == field.name)
+    }
+
+    /// the fields of the group that precede `field`
+    pub(super) fn fields_before(&self, field: &Rc<NetStructField>) -> &[Rc<NetStructField>] {
+        let i = self.fields.iter().position(|f| f.name == field.name).unwrap_or(0);
+        &self.fields[..i]
+    }
+
+    /// the length of the group in bytes as a `Result<usize, SerdeErr>`, given the value `len` of the
+    /// length field
+    pub(super) fn to_bytes(&self, len: TokenStream) -> TokenStream {
+        self.unit.to_bytes(&self.len_field.name, len)
+    }
+}
+
+impl SizeUnit {
+    pub(super) fn new(base: BaseUnit) -> Self {
+        Self {
+            base,
+            ..Default::default()
+        }
+    }
+
+    /// whether the argument of `len_of` is a unit rather than a field name, `len` is a field name
+    /// as a group cannot be measured in number of elements
+    pub(super) fn is_unit_arg(arg: &TokenStream) -> bool {
+        match arg.clone().into_iter().next() {
+            Some(proc_macro2::TokenTree::Ident(i)) => {
+                let s = i.to_string();
+                [WORDS_UNIT_PATH, OFFSET_UNIT_PATH, EXPR_UNIT_PATH].contains(&s.as_str())
+                    || size_unit_map().get(&s).is_some_and(|base| *base != BaseUnit::LENGTH)
+            }
+            _ => true,
+        }
+    }
+
+    /// parses the unit arguments of `vec_len`, `len_of` and `struct_len`, e.g. `bytes`, `words(4)`, `offset = -20`
+    /// or `expr = <EXPR>`
+    pub(super) fn parse(args: &[TokenStream], default: BaseUnit) -> Self {
         const UNEXPECTED_UNIT_MSG: &'static str = "Unexpected size unit";
-        if s.is_empty() {
-            return Self::default();
+        let mut unit = Self::new(default);
+        for arg in args {
+            let mut it = arg.clone().into_iter().peekable();
+            match expect_ident(&mut it, UNEXPECTED_UNIT_MSG).as_str() {
+                WORDS_UNIT_PATH => {
+                    unit.base = BaseUnit::BYTES;
+                    unit.scale = Some(parse_attr_group_arg(&mut it, WORDS_UNIT_PATH));
+                }
+                OFFSET_UNIT_PATH => {
+                    unit.offset = Some(NetStructField::parse_attr_value(&mut it, OFFSET_UNIT_PATH));
+                }
+                EXPR_UNIT_PATH => {
+                    unit.expr = Some(NetStructField::parse_attr_value(&mut it, EXPR_UNIT_PATH));
+                }
+                s => match size_unit_map().get(s) {
+                    Some(base) => unit.base = *base,
+                    None => panic!("{}: \"{}\"", UNEXPECTED_UNIT_MSG, s),
+                },
+            }
+        }
+        unit
+    }
+
+    /// the length in the base unit as a `Result<usize, SerdeErr>`, given the value `len` of the length field
+    /// named `len_name`
+    fn to_base(&self, len_name: &str, len: TokenStream) -> TokenStream {
+        let len = quote!(net_struct_serde::traits::LenField::to_len(&#len));
+        if let Some(expr) = &self.expr {
+            let name = TokenStream::from_str(len_name).unwrap();
+            let expr = TokenStream::from_str(expr.as_str()).unwrap();
+            return quote!((#len).map(|#name: usize| {
+                let _ = #name;
+                (#expr) as usize
+            }));
+        }
+        let mut ts = len;
+        if let Some(scale) = &self.scale {
+            let scale = TokenStream::from_str(scale.as_str()).unwrap();
+            ts = quote!((#ts).map(|l| l * (#scale) as usize));
+        }
+        if let Some(offset) = &self.offset {
+            let offset = TokenStream::from_str(offset.as_str()).unwrap();
+            ts = quote!((#ts).map(|l| l.checked_add_signed((#offset) as isize).unwrap_or(usize::MAX)));
+        }
+        ts
+    }
+
+    /// whether the length is a number of elements rather than a number of bytes or bits
+    pub(super) fn is_count(&self) -> bool {
+        self.base == BaseUnit::LENGTH
+    }
+
+    /// converts the value `len` of the length field named `len_name` into the number of elements, as a
+    /// `Result<usize, SerdeErr>`
+    pub(super) fn to_count(&self, len_name: &str, len: TokenStream) -> TokenStream {
+        assert!(self.is_count());
+        self.to_base(len_name, len)
+    }
+
+    /// a closure that converts the number of elements and the number of bytes of a vector into the value
+    /// of the length field, which is not possible if the length is given by `expr`
+    pub(super) fn from_seq_len(&self) -> Option<TokenStream> {
+        if self.expr.is_some() {
+            return None;
+        }
+        let mut ts = match self.base {
+            BaseUnit::BITS => quote!(bytes * 8),
+            BaseUnit::BYTES => quote!(bytes),
+            BaseUnit::LENGTH => quote!(count),
+        };
+        if let Some(offset) = &self.offset {
+            let offset = TokenStream::from_str(offset.as_str()).unwrap();
+            ts = quote!((#ts).checked_add_signed(-((#offset) as isize)).unwrap_or(usize::MAX));
         }
-        match size_unit_map().get(&s) {
-            Some(v) => *v,
-            None => panic!("{}", UNEXPECTED_UNIT_MSG),
+        if let Some(scale) = &self.scale {
+            let scale = TokenStream::from_str(scale.as_str()).unwrap();
+            ts = quote!((#ts / (#scale) as usize));
+        }
+        Some(quote!(|count: usize, bytes: usize| {
+            let _ = (count, bytes);
+            #ts
+        }))
+    }
+
+    /// converts the value `len` of the length field named `len_name` into the number of bytes, as a
+    /// `Result<usize, SerdeErr>`
+    pub(super) fn to_bytes(&self, len_name: &str, len: TokenStream) -> TokenStream {
+        let len = self.to_base(len_name, len);
+        match self.base {
+            BaseUnit::BITS => quote!((#len).map(|l| l / 8)),
+            BaseUnit::BYTES => len,
+            BaseUnit::LENGTH => unreachable!(),
         }
     }
 }
@@ -78,7 +318,7 @@ impl From<&syn::Field> for NetStructField {
             .expect("Doesn't support unnamed fields")
             .to_string();
         let mut s = Self {
-            _field: field.clone(),
+            field: field.clone(),
             name,
             net_struct_attr: Vec::new(),
             ty: match &field.ty {
@@ -89,25 +329,57 @@ impl From<&syn::Field> for NetStructField {
                 syn::Type::Path(ty) => NetStructFieldType::Val {
                     ty: ty.path.to_token_stream(),
                 },
-                _ => unimplemented!("only support Array(vector) or Path typed fields"),
+                syn::Type::Tuple(ty) => NetStructFieldType::Val {
+                    ty: ty.to_token_stream(),
+                },
+                _ => unimplemented!("only support Array(vector), Path or Tuple typed fields"),
             },
         };
-        parse_attr(&field.attrs, ATTR_PATH, |ts| s.parse_attr_vec_len(ts));
-        parse_attr(&field.attrs, ATTR_PATH, |ts| s.parse_attr_phantom(ts));
-        if s.is_vec() {
-            let NetStructFieldType::Arr { ty, capacity } = s.ty else {
-                panic!("Since this field is a vector, expected type to have an initial value of NetStructFieldType::Arr")
-            };
-            s.ty = NetStructFieldType::Vec {
-                ty: ty,
-                capacity: capacity,
-            }
-        }
+        parse_attr(&field.attrs, ATTR_PATH, |ts| s.parse_attrs(ts));
         s
     }
 }
 
 impl NetStructField {
+    /// turns the type of the field into a vector once all its attributes are known
+    pub(super) fn update_vec_type(&mut self) {
+        if let (true, NetStructFieldType::Arr { ty, capacity }) = (self.is_vec(), &self.ty) {
+            self.ty = NetStructFieldType::Vec {
+                ty: ty.clone(),
+                capacity: capacity.clone(),
+            };
+        }
+    }
+
+    pub(super) fn is_vec_type(&self) -> bool {
+        matches!(self.ty, NetStructFieldType::Vec { .. })
+    }
+
+    /// the vector fields whose length is declared on this field by `field_vec_len`
+    pub(super) fn vec_len_of(&self) -> Vec<(String, SizeUnit)> {
+        self.net_struct_attr
+            .iter()
+            .filter_map(|attr| match attr {
+                FieldAttr::VecLenOf { vec_field, unit } => Some((vec_field.clone(), unit.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// all the declared `(length field, unit)` of this vector field
+    pub(super) fn vec_lens(&self) -> Vec<(&String, &SizeUnit)> {
+        self.net_struct_attr
+            .iter()
+            .filter_map(|attr| match attr {
+                FieldAttr::Vec {
+                    vec_len_field,
+                    unit,
+                } => Some((vec_len_field, unit)),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub(super) fn is_vec(&self) -> bool {
         self.net_struct_attr
             .iter()
@@ -121,6 +393,17 @@ impl NetStructField {
             .is_some()
     }
 
+    /// all the declared `(fields, unit)` of the groups whose length is held by this field
+    pub(super) fn len_of(&self) -> Vec<(&Vec<String>, &SizeUnit)> {
+        self.net_struct_attr
+            .iter()
+            .filter_map(|attr| match attr {
+                FieldAttr::LenOf { fields, unit } => Some((fields, unit)),
+                _ => None,
+            })
+            .collect()
+    }
+
     pub(super) fn is_phantom(&self) -> bool {
         self.net_struct_attr
             .iter()
@@ -131,52 +414,304 @@ impl NetStructField {
             .is_some()
     }
 
-    fn parse_attr_phantom(&mut self, ts: &TokenStream) {
-        let phantom_attr = String::from(PHANTOM_ATTR_PATH);
-        let expect_attr_name_msg = format!("Expected identifier \"{}\"", PHANTOM_ATTR_PATH);
-        let mut it = ts.clone().into_iter().peekable();
-        while it.peek().is_some() {
-            if phantom_attr != expect_ident(&mut it, expect_attr_name_msg.as_str()) {
-                it = skip_until_punct(&mut it, ',');
-                if it.next().is_none() {
-                    break;
+    /// the constant value of the field, written by the serializer regardless of the field value
+    pub(super) fn const_value(&self) -> Option<TokenStream> {
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::Const(expr) => Some(TokenStream::from_str(expr.as_str()).unwrap()),
+            _ => None,
+        })
+    }
+
+    /// the functions `(encode, decode)` that map the field into/from its wire representation
+    pub(super) fn flavour(&self) -> Option<(TokenStream, TokenStream)> {
+        let ty = self.field.ty.to_token_stream();
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::With(module) => {
+                let module = TokenStream::from_str(module.as_str()).unwrap();
+                Some((quote!(#module::encode), quote!(#module::decode)))
+            }
+            FieldAttr::Flavour(flavour) => {
+                let flavour = TokenStream::from_str(flavour.as_str()).unwrap();
+                Some((
+                    quote!(<#flavour as net_struct_serde::traits::Flavour<#ty>>::encode),
+                    quote!(<#flavour as net_struct_serde::traits::Flavour<#ty>>::decode),
+                ))
+            }
+            _ => None,
+        })
+    }
+
+    /// the alignment of the end of the field relative to the start of the struct
+    pub(super) fn pad_to(&self) -> Option<&String> {
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::PadTo(pad_to) => Some(pad_to),
+            _ => None,
+        })
+    }
+
+    /// the number of zero bytes that the field is serialized into
+    pub(super) fn reserved(&self) -> Option<TokenStream> {
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::Reserved(len) => Some(TokenStream::from_str(len.as_str()).unwrap()),
+            _ => None,
+        })
+    }
+
+    /// the value of a phantom field, which is not deserialized
+    pub(super) fn default_value(&self) -> TokenStream {
+        self.net_struct_attr
+            .iter()
+            .find_map(|attr| match attr {
+                FieldAttr::Default(expr) => Some(TokenStream::from_str(expr.as_str()).unwrap()),
+                _ => None,
+            })
+            .unwrap_or(quote!(Default::default()))
+    }
+
+    /// the terminator of a sequence field, and what happens to it
+    pub(super) fn until(&self) -> Option<(TokenStream, TerminatorMode)> {
+        let terminator = self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::Until(expr) => Some(TokenStream::from_str(expr.as_str()).unwrap()),
+            _ => None,
+        })?;
+        let mode = self
+            .net_struct_attr
+            .iter()
+            .find_map(|attr| match attr {
+                FieldAttr::Terminator(mode) => Some(*mode),
+                _ => None,
+            })
+            .unwrap_or_default();
+        Some((terminator, mode))
+    }
+
+    /// the condition for an `Option` field to be present
+    pub(super) fn condition(&self) -> Option<TokenStream> {
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::If(expr) => Some(TokenStream::from_str(expr.as_str()).unwrap()),
+            _ => None,
+        })
+    }
+
+    /// whether the serializer checks that an `Option` field is present iff its condition holds
+    pub(super) fn is_check_if(&self) -> bool {
+        self.net_struct_attr.iter().any(|attr| *attr == FieldAttr::CheckIf)
+    }
+
+    /// the first and the last versions that the field is present in
+    pub(super) fn version_range(&self) -> Option<(Option<TokenStream>, Option<TokenStream>)> {
+        let find = |f: fn(&FieldAttr) -> Option<&String>| {
+            self.net_struct_attr
+                .iter()
+                .find_map(f)
+                .map(|expr| TokenStream::from_str(expr.as_str()).unwrap())
+        };
+        let since = find(|attr| match attr {
+            FieldAttr::Since(expr) => Some(expr),
+            _ => None,
+        });
+        let until = find(|attr| match attr {
+            FieldAttr::UntilVersion(expr) => Some(expr),
+            _ => None,
+        });
+        match (&since, &until) {
+            (None, None) => None,
+            _ => Some((since, until)),
+        }
+    }
+
+    /// how the `Option` field is encoded
+    pub(super) fn option_mode(&self) -> Option<&OptionMode> {
+        self.net_struct_attr.iter().find_map(|attr| match attr {
+            FieldAttr::Option(mode) => Some(mode),
+            _ => None,
+        })
+    }
+
+    /// the type `T` of a field of type `Option<T>`
+    pub(super) fn option_inner_ty(&self) -> Option<TokenStream> {
+        let syn::Type::Path(ty) = &self.field.ty else {
+            return None;
+        };
+        let segment = ty.path.segments.last()?;
+        let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+            return None;
+        };
+        match (segment.ident == "Option", args.args.first()) {
+            (true, Some(syn::GenericArgument::Type(inner))) => Some(inner.to_token_stream()),
+            _ => None,
+        }
+    }
+
+    fn parse_attrs(&mut self, ts: &TokenStream) {
+        let expect_attr_name_msg = format!("Expected a field attribute for \"{}\"", ATTR_PATH);
+        for arg in split_attr_args(ts) {
+            let mut it = arg.into_iter().peekable();
+            match expect_ident(&mut it, expect_attr_name_msg.as_str()).as_str() {
+                VEC_LEN_ATTR_PATH => {
+                    let (vec_len_field, unit) = Self::parse_attr_vec_len(&mut it, VEC_LEN_ATTR_PATH, BaseUnit::LENGTH);
+                    self.net_struct_attr.push(FieldAttr::Vec {
+                        vec_len_field,
+                        unit,
+                    });
+                }
+                FIELD_VEC_LEN_ATTR_PATH => {
+                    let (vec_field, unit) = Self::parse_attr_vec_len(&mut it, FIELD_VEC_LEN_ATTR_PATH, BaseUnit::LENGTH);
+                    self.net_struct_attr.push(FieldAttr::VecLenOf { vec_field, unit });
+                }
+                LEN_OF_ATTR_PATH => {
+                    let (fields, unit) = Self::parse_attr_len_of(&mut it);
+                    self.net_struct_attr.push(FieldAttr::LenOf { fields, unit });
+                }
+                PHANTOM_ATTR_PATH => self.net_struct_attr.push(FieldAttr::Phantom),
+                EXPECT_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, EXPECT_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Expect(expr));
+                }
+                RANGE_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, RANGE_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Range(expr));
+                }
+                VALIDATE_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, VALIDATE_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Validate(expr));
+                }
+                CONST_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, CONST_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Const(expr));
+                }
+                WITH_ATTR_PATH => {
+                    let module = Self::parse_attr_value(&mut it, WITH_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::With(module));
+                }
+                PAD_TO_ATTR_PATH => {
+                    let pad_to = parse_attr_group_arg(&mut it, PAD_TO_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::PadTo(pad_to));
+                }
+                RESERVED_ATTR_PATH => {
+                    let len = parse_attr_group_arg(&mut it, RESERVED_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Reserved(len));
+                }
+                DEFAULT_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, DEFAULT_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Default(expr));
+                }
+                // the terminator of an array, or the last version of the field (see `NetStruct::resolve_until`)
+                UNTIL_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, UNTIL_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Until(expr));
                 }
+                TERMINATOR_ATTR_PATH => {
+                    let mode = match parse_attr_group_arg(&mut it, TERMINATOR_ATTR_PATH).as_str() {
+                        "consume" => TerminatorMode::Consume,
+                        "include" => TerminatorMode::Include,
+                        "peek" => TerminatorMode::Peek,
+                        mode => panic!("Unexpected terminator mode: \"{}\", expected consume, include or peek", mode),
+                    };
+                    self.net_struct_attr.push(FieldAttr::Terminator(mode));
+                }
+                IF_ATTR_PATH => {
+                    // the condition can also be given as a string literal
+                    let expr = Self::parse_attr_value(&mut it, IF_ATTR_PATH);
+                    let expr = match syn::parse_str::<syn::LitStr>(expr.as_str()) {
+                        Ok(lit) => lit.value(),
+                        Err(_) => expr,
+                    };
+                    self.net_struct_attr.push(FieldAttr::If(expr));
+                }
+                CHECK_IF_ATTR_PATH => self.net_struct_attr.push(FieldAttr::CheckIf),
+                OPTION_ATTR_PATH => {
+                    let mode = Self::parse_attr_option(&mut it);
+                    self.net_struct_attr.push(FieldAttr::Option(mode));
+                }
+                SINCE_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, SINCE_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Since(expr));
+                }
+                UNTIL_VERSION_ATTR_PATH => {
+                    let expr = Self::parse_attr_value(&mut it, UNTIL_VERSION_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::UntilVersion(expr));
+                }
+                FLAVOUR_ATTR_PATH => {
+                    let flavour = Self::parse_attr_value(&mut it, FLAVOUR_ATTR_PATH);
+                    self.net_struct_attr.push(FieldAttr::Flavour(flavour));
+                }
+                _ => (),
             }
-            self.net_struct_attr.push(FieldAttr::Phantom);
         }
     }
 
-    fn parse_attr_vec_len(&mut self, ts: &TokenStream) {
-        let vec_len_attr = String::from(VEC_LEN_ATTR_PATH);
+    /// parses the `= <EXPR>` part of an attribute
+    pub(super) fn parse_attr_value<I>(it: &mut I, attr_path: &str) -> String
+    where
+        I: Iterator<Item = proc_macro2::TokenTree>,
+    {
+        let expect_eq_msg = format!("Expected \"=\" after \"{}\"", attr_path);
+        let expect_expr_msg = format!("Expected an expression for \"{}\"", attr_path);
+        expect_punct(it, '=', expect_eq_msg.as_str());
+        let expr = TokenStream::from_iter(it);
+        assert!(!expr.is_empty(), "{}", expect_expr_msg);
+        expr.to_string()
+    }
+
+    /// parses the `(<FIELD>, <OPTIONAL:LENGTH_UNIT>)` part of `vec_len`, `field_vec_len` and `struct_len`
+    pub(super) fn parse_attr_vec_len<I>(it: &mut I, attr_path: &str, default: BaseUnit) -> (String, SizeUnit)
+    where
+        I: Iterator<Item = proc_macro2::TokenTree>,
+    {
         let expect_group_msg = format!(
             "Expected parenthesis with arguments after \"{}\"",
-            VEC_LEN_ATTR_PATH
+            attr_path
         );
-        let expect_attr_name_msg = format!("Expected identifier \"{}\"", VEC_LEN_ATTR_PATH);
-        let expect_field_name_msg =
-            format!("Expected a vector field name for \"{}\"", VEC_LEN_ATTR_PATH);
+        let expect_field_name_msg = format!("Expected a field name for \"{}\"", attr_path);
 
-        let mut it = ts.clone().into_iter().peekable();
-        while it.peek().is_some() {
-            if vec_len_attr != expect_ident(&mut it, expect_attr_name_msg.as_str()) {
-                it = skip_until_punct(&mut it, ',');
-                if it.next().is_none() {
-                    break;
-                }
-            }
-            let mut arg_it =
-                expect_group(&mut it, Delimiter::Parenthesis, expect_group_msg.as_str())
-                    .into_iter()
-                    .peekable();
-            let vec_len_field_name = expect_ident(&mut arg_it, expect_field_name_msg.as_str());
-            consume_punct(&mut arg_it, ',');
-            let len_unit = consume_ident(&mut arg_it)
-                .map(|s| SizeUnit::from(s))
-                .unwrap_or(SizeUnit::LENGTH);
-            self.net_struct_attr.push(FieldAttr::Vec {
-                vec_len_field: vec_len_field_name,
-                unit: len_unit,
-            });
+        let args = split_attr_args(&expect_group(it, Delimiter::Parenthesis, expect_group_msg.as_str()));
+        let Some(field_name) = args.first() else {
+            panic!("{}", expect_field_name_msg);
+        };
+        let field_name = expect_ident(&mut field_name.clone().into_iter(), expect_field_name_msg.as_str());
+        (field_name, SizeUnit::parse(&args[1..], default))
+    }
+
+    /// parses the `(<FIELDS>, <OPTIONAL:LENGTH_UNIT>)` part of `len_of`, where `FIELDS` is either
+    /// a list of field names or `self`
+    fn parse_attr_len_of<I>(it: &mut I) -> (Vec<String>, SizeUnit)
+    where
+        I: Iterator<Item = proc_macro2::TokenTree>,
+    {
+        let expect_group_msg = format!(
+            "Expected parenthesis with arguments after \"{}\"",
+            LEN_OF_ATTR_PATH
+        );
+        let expect_field_name_msg = format!("Expected a field name for \"{}\"", LEN_OF_ATTR_PATH);
+
+        let args = split_attr_args(&expect_group(it, Delimiter::Parenthesis, expect_group_msg.as_str()));
+        let (units, fields): (Vec<TokenStream>, Vec<TokenStream>) =
+            args.into_iter().partition(SizeUnit::is_unit_arg);
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|f| expect_ident(&mut f.into_iter(), expect_field_name_msg.as_str()))
+            .collect();
+        assert!(!fields.is_empty(), "{}", expect_field_name_msg);
+        (fields, SizeUnit::parse(&units, BaseUnit::BYTES))
+    }
+
+    /// parses the `(<MODE>)` part of `option`
+    fn parse_attr_option<I>(it: &mut I) -> OptionMode
+    where
+        I: Iterator<Item = proc_macro2::TokenTree>,
+    {
+        const EXPECT_MODE_MSG: &'static str =
+            "Expected presence, sentinel = <EXPR>, len_prefix = <TYPE> or trailing for \"option\"";
+        let expect_group_msg = format!("Expected parenthesis with an argument after \"{}\"", OPTION_ATTR_PATH);
+        let arg = expect_group(it, Delimiter::Parenthesis, expect_group_msg.as_str());
+        let mut arg = arg.into_iter().peekable();
+        match expect_ident(&mut arg, EXPECT_MODE_MSG).as_str() {
+            "presence" => OptionMode::Presence,
+            "trailing" => OptionMode::Trailing,
+            "sentinel" => OptionMode::Sentinel(Self::parse_attr_value(&mut arg, "sentinel")),
+            "len_prefix" => OptionMode::LenPrefix(Self::parse_attr_value(&mut arg, "len_prefix")),
+            _ => panic!("{}", EXPECT_MODE_MSG),
         }
     }
 }
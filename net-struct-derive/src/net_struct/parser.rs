@@ -3,9 +3,9 @@ use std::str::FromStr;
 use crate::err::DeriveErr;
 
 use super::*;
-use field::NetStructFieldType;
+use field::{FieldAttr, LenOfGroup, NetStructFieldType, OptionMode, TerminatorMode};
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use std::collections::{HashMap, HashSet, VecDeque};
 
 struct DeserializeFieldIter {
@@ -13,11 +13,21 @@ struct DeserializeFieldIter {
     unread_fields: VecDeque<Rc<NetStructField>>,
     read_fields: HashSet<String>,
     vec_fields: HashMap<String, VecField>,
+    len_of_groups: Vec<LenOfGroup>,
+    condition_deps: HashMap<String, Vec<String>>,
     direction: bool,
 }
 
 impl DeserializeFieldIter {
     fn is_deserializable(&self, field: &Rc<NetStructField>) -> bool {
+        // the condition of a field can only be evaluated after the fields it refers to
+        let already_read_deps = self
+            .condition_deps
+            .get(&field.name)
+            .is_none_or(|deps| deps.iter().all(|d| self.read_fields.contains(d)));
+        if !already_read_deps {
+            return false;
+        }
         let Some(v_f) = self.vec_fields.get(&field.name) else {
             return true;
         };
@@ -26,8 +36,16 @@ impl DeserializeFieldIter {
             Some((ref f, _)) => self.read_fields.contains(&f.name),
             None => true,
         };
+        // a vector taking the rest of a group can only be read after the rest of the group
+        let already_read_group = self
+            .len_of_groups
+            .iter()
+            .filter(|g| g.len_field.name == v_f.len_field.name)
+            .all(|g| g.fields_before(field).iter().all(|f| self.read_fields.contains(&f.name)));
         let is_sole_vec = self.unread_fields.len() == 1 && field.is_vec();
-        already_read_len_field && already_read_struct_len || is_sole_vec || field.is_phantom()
+        already_read_len_field && already_read_struct_len && already_read_group
+            || is_sole_vec
+            || field.is_phantom()
     }
 
     pub fn new(net_struct: &NetStruct) -> Self {
@@ -36,6 +54,20 @@ impl DeserializeFieldIter {
             unread_fields: VecDeque::from_iter(net_struct.fields.iter().map(|f| f.clone())),
             read_fields: HashSet::new(),
             vec_fields: net_struct.find_all_vec_fields(),
+            len_of_groups: net_struct.find_all_len_of_groups(),
+            condition_deps: net_struct
+                .fields
+                .iter()
+                .map(|f| {
+                    let deps = net_struct.condition_deps(f);
+                    // the phantom fields are initialised before any field is deserialized
+                    let deps = deps
+                        .into_iter()
+                        .filter(|d| net_struct.find_field_from_name(d.clone()).is_some_and(|d| !d.is_phantom()))
+                        .collect();
+                    (f.name.clone(), deps)
+                })
+                .collect(),
             direction: true,
         }
     }
@@ -90,19 +122,33 @@ impl std::iter::Iterator for DeserializeFieldIter {
 }
 
 impl NetStruct {
-    const UNINIT_STRUCT_VAR: &'static str = "s";
+    const STRUCT_START_VAR: &'static str = "struct_start";
 
-    fn truncate_size(&self) -> TokenStream {
-        let var = TokenStream::from_str(Self::UNINIT_STRUCT_VAR).unwrap();
-        if let Some((ref f, unit)) = self.attrs.struct_len {
-            let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
-            match unit {
-                SizeUnit::BITS => quote!(.truncate(#var.assume_init_ref().#field_name as usize / 8)?),
-                SizeUnit::BYTES => quote!(.truncate(#var.assume_init_ref().#field_name as usize)?),
-                SizeUnit::LENGTH => unreachable!(),
-            }
-        } else {
-            TokenStream::new()
+    /// truncates the deserializer to the end of the struct, right after the `struct_len` field is deserialized
+    fn truncate_size(&self, field: &Rc<NetStructField>, dir: bool) -> Result<TokenStream, DeriveErr> {
+        let Some((ref f, ref unit)) = self.attrs.struct_len else {
+            return Ok(TokenStream::new());
+        };
+        if f.name != field.name {
+            return Ok(TokenStream::new());
+        }
+        let start = TokenStream::from_str(Self::STRUCT_START_VAR).unwrap();
+        let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+        let len = unit.to_bytes(&f.name, quote!(#field_name));
+        match dir {
+            true => Ok(quote! {
+                let deserializer = {
+                    let consumed = deserializer.offset() - #start;
+                    let remaining = (#len)?
+                        .checked_sub(consumed)
+                        .ok_or(net_struct_serde::SerdeErr::InvalidValue(stringify!(#field_name)))?;
+                    deserializer.truncate(remaining)?
+                };
+            }),
+            false => Err(DeriveErr::Message(format!(
+                "Unable to deserialize the struct_len field \"{}\" of the structure \"{}\" in reverse direction",
+                &f.name,
+                self.derive_input.ident.to_string()))),
         }
     }
 
@@ -115,9 +161,7 @@ impl NetStruct {
         ty: &TokenStream,
         _capacity: &String,
     ) -> Result<TokenStream, DeriveErr> {
-        let var = TokenStream::from_str(Self::UNINIT_STRUCT_VAR).unwrap();
-        let field_name_str = field.name.as_str();
-        let field_name = TokenStream::from_str(field_name_str).unwrap();
+        let field_name = TokenStream::from_str(field.name.as_str()).unwrap();
         let Some(vec_field) = vec_fields.get(&field.name) else {
             return Err(DeriveErr::Message(format!(
                 "Unexpected error when implementing Deserialize for the vector field \"{}\" of the structure \"{}\"", 
@@ -126,34 +170,154 @@ impl NetStruct {
         };
         let len = TokenStream::from_str(vec_field.len_field.name.as_str()).unwrap();
 
+        if let Some(group) = self.find_len_of_group(vec_field) {
+            let start = group.start_var();
+            let group_len = group.to_bytes(quote!(#len));
+            return match dir {
+                true => Ok(quote! {
+                    let deserializer = {
+                        let consumed = deserializer.offset() - #start;
+                        let remaining = (#group_len)?
+                            .checked_sub(consumed)
+                            .ok_or(net_struct_serde::SerdeErr::InvalidValue(stringify!(#len)))?;
+                        deserializer.deserialize_seq_bytes::<#ty, &mut [#ty]>(&mut #field_name, remaining)?.0
+                    };
+                }),
+                false => Err(self.len_of_reverse_err(&group)),
+            };
+        }
+
         match vec_field.len_field.is_phantom() {
             true => {
-                let len_adj = match vec_field.len_unit {
-                    SizeUnit::BITS => quote!( |l| l / (8_usize * core::mem::size_of::<#ty>())),
-                    SizeUnit::BYTES => quote!( |l| l / core::mem::size_of::<#ty>()),
-                    SizeUnit::LENGTH => quote!(|l| l),
+                let Some(len_adj) = vec_field.len_unit.from_seq_len() else {
+                    return Err(DeriveErr::Message(format!(
+                        "The phantom length field \"{}\" of the structure \"{}\" cannot be given by an expression",
+                        &vec_field.len_field.name,
+                        self.derive_input.ident.to_string())));
                 };
-                Ok(match dir {
-                    true => quote! {
-                        .deserialize_seq_until_end::<#ty, &mut [#ty]>(&mut (*#var.as_mut_ptr()).#field_name, &mut (*#var.as_mut_ptr()).#len, #len_adj)?
-                    },
-                    false => quote! {
-                        .reverse()?
-                        .deserialize_seq_until_end::<#ty, &mut [#ty]>(&mut (*#var.as_mut_ptr()).#field_name, &mut (*#var.as_mut_ptr()).#len, #len_adj)?
-                    },
+                let reverse = match dir {
+                    true => TokenStream::new(),
+                    false => quote!(.reverse()?),
+                };
+                Ok(quote! {
+                    let deserializer = deserializer
+                        #reverse
+                        .deserialize_seq_until_end::<#ty, &mut [#ty], _>(&mut #field_name, &mut #len, #len_adj)?;
                 })
             }
-            false => {
-                let unit = match vec_field.len_unit {
-                    SizeUnit::BITS => quote!(as usize / (8_usize * core::mem::size_of::<#ty>())),
-                    SizeUnit::BYTES => quote!(as usize / core::mem::size_of::<#ty>()),
-                    SizeUnit::LENGTH => quote!(as usize),
+            false if vec_field.len_unit.is_count() => {
+                let count = vec_field.len_unit.to_count(&vec_field.len_field.name, quote!(#len));
+                let seq = match is_primitive(ty) {
+                    true => quote!(deserialize_primitive_seq),
+                    false => quote!(deserialize_seq),
                 };
                 Ok(quote! {
-                    .deserialize_seq::<#ty, &mut [#ty]>(&mut (*#var.as_mut_ptr()).#field_name, #var.assume_init_ref().#len #unit)?
+                    let deserializer = deserializer.#seq::<#ty, &mut [#ty]>(&mut #field_name, (#count)?)?;
                 })
             }
+            false => {
+                let bytes = vec_field.len_unit.to_bytes(&vec_field.len_field.name, quote!(#len));
+                Ok(quote! {
+                    let (deserializer, _) = deserializer.deserialize_seq_bytes::<#ty, &mut [#ty]>(&mut #field_name, (#bytes)?)?;
+                })
+            }
+        }
+    }
+
+    /// deserializes an `Option` field in the given encoding
+    fn deserialize_option(&self, field: &Rc<NetStructField>, dir: bool, mode: &OptionMode) -> Result<TokenStream, DeriveErr> {
+        let field_name_str = field.name.as_str();
+        let field_name = TokenStream::from_str(field_name_str).unwrap();
+        let struct_name = self.derive_input.ident.to_string();
+        let Some(ty) = field.option_inner_ty().filter(|_| field.condition().is_none()) else {
+            return Err(DeriveErr::Message(format!(
+                "The field \"{}\" of the structure \"{}\" with an option encoding must be an Option without a condition",
+                field_name_str, struct_name)));
+        };
+        let is_last = self.fields.iter().rev().find(|f| !f.is_phantom()).is_some_and(|f| f.name == field.name);
+        match (mode, dir) {
+            (OptionMode::Presence | OptionMode::LenPrefix(_) | OptionMode::Trailing, false) => {
+                return Err(DeriveErr::Message(format!(
+                    "Unable to deserialize the Option field \"{}\" of the structure \"{}\" in reverse direction",
+                    field_name_str, struct_name)));
+            }
+            (OptionMode::Trailing, true) if !is_last => {
+                return Err(DeriveErr::Message(format!(
+                    "The trailing Option field \"{}\" of the structure \"{}\" is not the last field",
+                    field_name_str, struct_name)));
+            }
+            _ => (),
         }
+        Ok(match mode {
+            OptionMode::Presence => quote! {
+                let (deserializer, present) = deserializer.deserialize_value::<u8>(#field_name_str)?;
+                let (deserializer, #field_name) = match present {
+                    0 => (deserializer, None),
+                    1 => {
+                        let (deserializer, v) = deserializer.deserialize_value::<#ty>(#field_name_str)?;
+                        (deserializer, Some(v))
+                    }
+                    _ => return Err(net_struct_serde::SerdeErr::InvalidValue(#field_name_str)),
+                };
+            },
+            OptionMode::Sentinel(sentinel) => {
+                let sentinel = TokenStream::from_str(sentinel.as_str()).unwrap();
+                quote! {
+                    let (deserializer, v) = deserializer.deserialize_value::<#ty>(#field_name_str)?;
+                    let #field_name = match v == (#sentinel) {
+                        true => None,
+                        false => Some(v),
+                    };
+                }
+            }
+            OptionMode::LenPrefix(len_ty) => {
+                let len_ty = TokenStream::from_str(len_ty.as_str()).unwrap();
+                quote! {
+                    let (deserializer, len) = deserializer.deserialize_value::<#len_ty>(#field_name_str)?;
+                    let (deserializer, #field_name) = match net_struct_serde::traits::LenField::to_len(&len)? {
+                        0 => (deserializer, None),
+                        len => {
+                            let start = deserializer.offset();
+                            let (deserializer, v) = deserializer.deserialize_value::<#ty>(#field_name_str)?;
+                            if deserializer.offset() - start != len {
+                                return Err(net_struct_serde::SerdeErr::InvalidValue(#field_name_str));
+                            }
+                            (deserializer, Some(v))
+                        }
+                    };
+                }
+            }
+            OptionMode::Trailing => quote! {
+                let (deserializer, #field_name) = deserializer.deserialize_value::<Option<#ty>>(#field_name_str)?;
+            },
+        })
+    }
+
+    /// deserializes a sequence field until its terminator
+    fn deserialize_until(
+        &self,
+        field: &Rc<NetStructField>,
+        dir: bool,
+        terminator: TokenStream,
+        mode: TerminatorMode,
+    ) -> Result<TokenStream, DeriveErr> {
+        let field_name = TokenStream::from_str(field.name.as_str()).unwrap();
+        let field_ty = field.field.ty.to_token_stream();
+        let mode = mode.to_variant();
+        let (NetStructFieldType::Arr { ty, .. }, true) = (&field.ty, dir) else {
+            return Err(DeriveErr::Message(format!(
+                "The terminated field \"{}\" of the structure \"{}\" must be an array without a length field, deserialized forward",
+                &field.name,
+                self.derive_input.ident.to_string())));
+        };
+        Ok(quote! {
+            let mut #field_name: #field_ty = net_struct_serde::traits::Placeholder::placeholder();
+            let (deserializer, _) = deserializer.deserialize_seq_until::<#ty, &mut [#ty]>(
+                &mut #field_name,
+                &(#terminator),
+                #mode,
+            )?;
+        })
     }
 
     fn deserialize_one_field(
@@ -163,44 +327,260 @@ impl NetStruct {
         direction_changed: bool,
         vec_fields: HashMap<String, VecField>,
     ) -> Result<TokenStream, DeriveErr> {
-        let var = TokenStream::from_str(Self::UNINIT_STRUCT_VAR).unwrap();
         let field_name_str = field.name.as_str();
         let field_name = TokenStream::from_str(field_name_str).unwrap();
+        let field_ty = field.field.ty.to_token_stream();
         let mut ts = match direction_changed {
-            true => quote!(.reverse()?),
+            true => quote!(let deserializer = deserializer.reverse()?;),
             false => quote!(),
         };
         if field.is_phantom() {
             return Ok(ts);
         }
+        if let Some(len) = field.reserved() {
+            ts.extend(quote! {
+                let deserializer = deserializer.skip(#len)?;
+                let #field_name: #field_ty = Default::default();
+            });
+            return Ok(ts);
+        }
+        if let Some(condition) = field.condition() {
+            let Some(ty) = field.option_inner_ty() else {
+                return Err(DeriveErr::Message(format!(
+                    "The conditional field \"{}\" of the structure \"{}\" must be an Option",
+                    field_name_str,
+                    self.derive_input.ident.to_string())));
+            };
+            ts.extend(quote! {
+                let (deserializer, #field_name) = match #condition {
+                    true => {
+                        let (deserializer, v) = deserializer.deserialize_value::<#ty>(#field_name_str)?;
+                        (deserializer, Some(v))
+                    }
+                    false => (deserializer, None),
+                };
+            });
+            return Ok(ts);
+        }
+        if let Some(mode) = field.option_mode() {
+            ts.extend(self.deserialize_option(&field, dir, mode)?);
+            return Ok(ts);
+        }
+        if let Some((_, decode)) = field.flavour() {
+            ts.extend(quote! {
+                let (deserializer, wire) = deserializer.deserialize_value(#field_name_str)?;
+                let #field_name = #decode(wire)?;
+            });
+            return Ok(ts);
+        }
+        if let Some((terminator, mode)) = field.until() {
+            ts.extend(self.deserialize_until(&field, dir, terminator, mode)?);
+            return Ok(ts);
+        }
         ts.extend(match &field.ty {
             NetStructFieldType::Val { ty } => quote!{
-                .deserialize_field::<#ty>(&mut (*#var.as_mut_ptr()).#field_name, #field_name_str)?
+                let (deserializer, #field_name) = deserializer.deserialize_value::<#ty>(#field_name_str)?;
             },
             NetStructFieldType::Arr { ty, capacity } => {
                 let capacity_ts = TokenStream::from_str(capacity.as_str()).unwrap();
+                let seq = match is_primitive(ty) {
+                    true => quote!(deserialize_primitive_seq),
+                    false => quote!(deserialize_seq),
+                };
                 quote! {
-                    .deserialize_seq::<#ty, &mut [#ty]>(&mut (*#var.as_mut_ptr()).#field_name, #capacity_ts as usize)?
+                    let mut #field_name: #field_ty = net_struct_serde::traits::Placeholder::placeholder();
+                    let deserializer = deserializer.#seq::<#ty, &mut [#ty]>(&mut #field_name, #capacity_ts as usize)?;
                 }
             },
             NetStructFieldType::Vec { ty, capacity } => {
-                self.deserialize_vec(&field, dir, direction_changed, &vec_fields, ty, capacity)?
+                let mut vec_ts = quote! {
+                    let mut #field_name: #field_ty = net_struct_serde::traits::Placeholder::placeholder();
+                };
+                vec_ts.extend(self.deserialize_vec(&field, dir, direction_changed, &vec_fields, ty, capacity)?);
+                vec_ts
             },
         });
         Ok(ts)
     }
 
+    /// initialises the phantom fields, which are not deserialized
+    fn init_phantom_fields(&self) -> TokenStream {
+        let vec_fields = self.find_all_vec_fields();
+        let mut ts = TokenStream::new();
+        for f in self.fields.iter().filter(|f| f.is_phantom()) {
+            let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+            let field_ty = f.field.ty.to_token_stream();
+            let default = f.default_value();
+            // the phantom version field is given by the deserializer
+            let is_version_field = self.attrs.version_field.as_ref().is_some_and(|v| v.name == f.name);
+            let default = match is_version_field {
+                true => quote! {
+                    match deserializer.version() {
+                        Some(v) => <#field_ty as core::convert::TryFrom<u32>>::try_from(v)
+                            .map_err(|_| net_struct_serde::SerdeErr::OutOfRange)?,
+                        None => #default,
+                    }
+                },
+                false => default,
+            };
+            // the phantom length field of a vector is set by `deserialize_seq_until_end`
+            let is_len_field = vec_fields.values().any(|v_f| v_f.len_field.name == f.name);
+            ts.extend(match is_len_field {
+                true => quote!(let mut #field_name: #field_ty = #default;),
+                false => quote!(let #field_name: #field_ty = #default;),
+            });
+        }
+        ts
+    }
+
+    /// checks the constraints of a deserialized field
+    pub(super) fn check_field(&self, field: &Rc<NetStructField>) -> TokenStream {
+        let field_name_str = field.name.as_str();
+        let value = TokenStream::from_str(field_name_str).unwrap();
+        let mut ts = TokenStream::new();
+        if field.is_phantom() {
+            return ts;
+        }
+        for attr in field.net_struct_attr.iter() {
+            let is_valid = match attr {
+                FieldAttr::Expect(expr) | FieldAttr::Const(expr) => {
+                    let expr = TokenStream::from_str(expr.as_str()).unwrap();
+                    quote!(#value == (#expr))
+                }
+                FieldAttr::Range(expr) => {
+                    let expr = TokenStream::from_str(expr.as_str()).unwrap();
+                    quote!((#expr).contains(&#value))
+                }
+                FieldAttr::Validate(expr) => {
+                    let expr = TokenStream::from_str(expr.as_str()).unwrap();
+                    quote!(#expr(&#value))
+                }
+                _ => continue,
+            };
+            ts.extend(quote! {
+                if !(#is_valid) {
+                    return Err(net_struct_serde::SerdeErr::InvalidValue(#field_name_str));
+                }
+            });
+        }
+        ts
+    }
+
+    /// skips the padding from the current offset to a multiple of `pad_to`
+    fn skip_padding(&self, pad_to: &String, dir: bool, padded: &str) -> Result<TokenStream, DeriveErr> {
+        let start = TokenStream::from_str(Self::STRUCT_START_VAR).unwrap();
+        let pad_to = TokenStream::from_str(pad_to.as_str()).unwrap();
+        match dir {
+            true => Ok(quote! {
+                let deserializer = {
+                    let offset = deserializer.offset() - #start;
+                    deserializer.skip((#pad_to - offset % #pad_to) % #pad_to)?
+                };
+            }),
+            false => Err(DeriveErr::Message(format!(
+                "Unable to deserialize the padding of \"{}\" of the structure \"{}\" in reverse direction",
+                padded,
+                self.derive_input.ident.to_string()))),
+        }
+    }
+
+    fn len_of_reverse_err(&self, group: &LenOfGroup) -> DeriveErr {
+        DeriveErr::Message(format!(
+            "Unable to deserialize the group of the length field \"{}\" of the structure \"{}\" in reverse direction",
+            &group.len_field.name,
+            self.derive_input.ident.to_string()))
+    }
+
+    /// records the offset of the start of the groups that begin with `field`
+    fn init_group_start(&self, field: &Rc<NetStructField>, dir: bool, dir_changed: bool, groups: &[LenOfGroup]) -> Result<TokenStream, DeriveErr> {
+        let mut ts = TokenStream::new();
+        for group in groups.iter().filter(|g| g.contains(field)) {
+            if !dir || dir_changed {
+                return Err(self.len_of_reverse_err(group));
+            }
+            if group.is_first(field) {
+                let start = group.start_var();
+                ts.extend(quote!(let #start = deserializer.offset();));
+            }
+        }
+        Ok(ts)
+    }
+
+    /// skips to the end of the groups that end with `field`, if their length fields are already read
+    fn bound_group_end(&self, field: &Rc<NetStructField>, groups: &[LenOfGroup], read_fields: &HashSet<String>) -> TokenStream {
+        let mut ts = TokenStream::new();
+        for group in groups.iter().filter(|g| g.is_last(field) && read_fields.contains(&g.len_field.name)) {
+            let start = group.start_var();
+            let len = TokenStream::from_str(group.len_field.name.as_str()).unwrap();
+            let group_len = group.to_bytes(quote!(#len));
+            ts.extend(quote! {
+                let deserializer = {
+                    let consumed = deserializer.offset() - #start;
+                    let remaining = (#group_len)?
+                        .checked_sub(consumed)
+                        .ok_or(net_struct_serde::SerdeErr::InvalidValue(stringify!(#len)))?;
+                    deserializer.skip(remaining)?
+                };
+            });
+        }
+        ts
+    }
+
+    /// only deserializes the field with `field_ts` if it is present in the version, otherwise sets it to
+    /// its default value
+    fn gate_field(&self, field: &Rc<NetStructField>, gate: TokenStream, field_ts: TokenStream) -> TokenStream {
+        let field_name = TokenStream::from_str(field.name.as_str()).unwrap();
+        let default = field.default_value();
+        quote! {
+            let (deserializer, #field_name) = match #gate {
+                true => {
+                    #field_ts
+                    (deserializer, #field_name)
+                }
+                false => (deserializer, #default),
+            };
+        }
+    }
+
     fn deserialize_fields(&self) -> Result<TokenStream, DeriveErr> {
         let field_iter = DeserializeFieldIter::new(self);
+        let groups = self.find_all_len_of_groups();
+        let mut read_fields = HashSet::new();
         let mut ts = TokenStream::new();
+        let mut last_dir = true;
         for field in field_iter {
             let (f, dir, dir_changed) = field?;
-            ts.extend(self.deserialize_one_field(
-                f,
-                dir,
-                dir_changed,
-                self.find_all_vec_fields(),
-            )?);
+            read_fields.insert(f.name.clone());
+            ts.extend(self.init_group_start(&f, dir, dir_changed, &groups)?);
+            match self.version_gate(&f).filter(|_| !f.is_phantom()) {
+                Some(gate) => {
+                    if dir_changed {
+                        ts.extend(quote!(let deserializer = deserializer.reverse()?;));
+                    }
+                    let mut field_ts = self.deserialize_one_field(f.clone(), dir, false, self.find_all_vec_fields())?;
+                    field_ts.extend(self.check_field(&f));
+                    ts.extend(self.gate_field(&f, gate, field_ts));
+                }
+                None => {
+                    ts.extend(self.deserialize_one_field(
+                        f.clone(),
+                        dir,
+                        dir_changed,
+                        self.find_all_vec_fields(),
+                    )?);
+                    ts.extend(self.check_field(&f));
+                }
+            }
+            ts.extend(self.truncate_size(&f, dir)?);
+            ts.extend(self.bound_group_end(&f, &groups, &read_fields));
+            if let Some(pad_to) = f.pad_to() {
+                ts.extend(self.skip_padding(pad_to, dir, f.name.as_str())?);
+            }
+            last_dir = dir;
+        }
+        if let Some(pad_to) = &self.attrs.pad_to {
+            let struct_name = self.derive_input.ident.to_string();
+            ts.extend(self.skip_padding(pad_to, last_dir, struct_name.as_str())?);
         }
         Ok(ts)
     }
@@ -210,21 +590,27 @@ impl NetStruct {
      */
     pub(super) fn parser(&self) -> Result<TokenStream, DeriveErr> {
         let struct_name = &self.derive_input.ident;
-        let var = TokenStream::from_str(Self::UNINIT_STRUCT_VAR).unwrap();
-        let trunc = self.truncate_size();
+        let phantom_fields = self.init_phantom_fields();
         let fields = self.deserialize_fields()?;
+        let start = TokenStream::from_str(Self::STRUCT_START_VAR).unwrap();
+        let init_start = match self.is_padded() || self.attrs.struct_len.is_some() {
+            true => quote!(let #start = deserializer.offset();),
+            false => TokenStream::new(),
+        };
+        let field_names = self
+            .fields
+            .iter()
+            .map(|f| TokenStream::from_str(f.name.as_str()).unwrap());
         Ok(quote! {
             impl net_struct_serde::traits::Deserialize for #struct_name {
                 fn deserialize<D>(deserializer: D) -> Result<Self, net_struct_serde::SerdeErr>
                     where D: net_struct_serde::traits::Deserializer
                 {
-                    let mut #var = core::mem::MaybeUninit::<#struct_name>::uninit();
-                    unsafe {
-                        deserializer
-                            #trunc
-                            #fields;
-                        Ok(s.assume_init())
-                    }
+                    #init_start
+                    #phantom_fields
+                    #fields
+                    let _ = deserializer;
+                    Ok(Self { #(#field_names),* })
                 }
             }
         })
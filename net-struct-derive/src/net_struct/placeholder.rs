@@ -0,0 +1,31 @@
+use std::str::FromStr;
+
+use super::*;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+impl NetStruct {
+    /**
+     * writes the implements of Placeholder for the NetStruct
+     */
+    pub(super) fn placeholder(&self) -> Result<TokenStream, DeriveErr> {
+        let mut fields_ts = TokenStream::new();
+        for f in self.fields.iter() {
+            let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+            let field_ty = f.field.ty.to_token_stream();
+            let value = match f.is_phantom() || f.reserved().is_some() {
+                true => f.default_value(),
+                false => quote!(<#field_ty as net_struct_serde::traits::Placeholder>::placeholder()),
+            };
+            fields_ts.extend(quote!(#field_name: #value,));
+        }
+        let struct_name = &self.derive_input.ident;
+        Ok(quote! {
+            impl net_struct_serde::traits::Placeholder for #struct_name {
+                fn placeholder() -> Self {
+                    Self { #fields_ts }
+                }
+            }
+        })
+    }
+}
@@ -1,55 +1,166 @@
-use field::NetStructFieldType;
+use field::{LenOfGroup, NetStructFieldType, OptionMode, TerminatorMode};
 use quote::quote;
 use std::str::FromStr;
 
 use super::*;
 use crate::err::*;
 
+const SERIALIZER_VAR_NAME: &'static str = "tup";
+const OFFSET_VAR_NAME: &'static str = "offset";
+
 impl NetStruct {
+    /// whether the offset from the start of the struct is needed for the padding or the `len_of` groups
+    fn tracks_offset(&self) -> bool {
+        self.is_padded() || !self.find_all_len_of_groups().is_empty()
+    }
+
+    /// serializes one element, and keeps track of the offset from the start of the struct if needed, by
+    /// counting the bytes written for the element
+    fn serialize_element(&self, ty: Option<&TokenStream>, elem: TokenStream) -> TokenStream {
+        let var = TokenStream::from_str(SERIALIZER_VAR_NAME).unwrap();
+        let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
+        let ty = ty.map(|ty| quote!(::<#ty>));
+        match self.tracks_offset() {
+            true => quote! {
+                #var.serialize_element(&net_struct_serde::Counted #ty (#elem, core::cell::Cell::from_mut(&mut #offset)))?;
+            },
+            false => quote! {
+                #var.serialize_element #ty (#elem)?;
+            },
+        }
+    }
+
+    /// serializes `len` zero bytes
+    fn serialize_zeros(&self, len: TokenStream) -> TokenStream {
+        let var = TokenStream::from_str(SERIALIZER_VAR_NAME).unwrap();
+        let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
+        let mut ts = quote! {
+            let len: usize = #len;
+            for _ in 0..len {
+                #var.serialize_element::<u8>(&0u8)?;
+            }
+        };
+        if self.tracks_offset() {
+            ts.extend(quote!(#offset += len;));
+        }
+        quote!({ #ts })
+    }
+
+    /// serializes the zero bytes that pads the struct from `offset` to a multiple of `pad_to`
+    fn serialize_padding(&self, pad_to: &String) -> TokenStream {
+        let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
+        let pad_to = TokenStream::from_str(pad_to.as_str()).unwrap();
+        self.serialize_zeros(quote!((#pad_to - #offset % #pad_to) % #pad_to))
+    }
+
+    /// pads the groups that end with `f` with zero bytes up to their length, if their length fields precede
+    /// their ends, as the deserializer skips to the end of such groups
+    fn serialize_group_end(&self, f: &Rc<NetStructField>, groups: &[LenOfGroup]) -> TokenStream {
+        let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
+        let position = |name: &String| self.fields.iter().position(|f| &f.name == name);
+        let mut ts = TokenStream::new();
+        for group in groups
+            .iter()
+            .filter(|g| g.is_last(f) && position(&g.len_field.name) <= position(&f.name))
+        {
+            let start = group.start_var();
+            let len_field = TokenStream::from_str(group.len_field.name.as_str()).unwrap();
+            let group_len = group.to_bytes(quote!(self.#len_field));
+            ts.extend(self.serialize_zeros(quote!((#group_len).map_err(<S::Error as serde::ser::Error>::custom)?.saturating_sub(#offset - #start))));
+        }
+        ts
+    }
+
+    /// fails if the `Option` field `f` is present but its condition does not hold, or vice versa
+    fn check_condition(&self, f: &Rc<NetStructField>, condition: TokenStream) -> TokenStream {
+        let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+        let field_name_str = f.name.as_str();
+        let deps = self.bind_condition_deps(f);
+        quote! {
+            {
+                #deps
+                if self.#field_name.is_some() != (#condition) {
+                    return Err(<S::Error as serde::ser::Error>::custom(
+                        net_struct_serde::SerdeErr::InvalidValue(#field_name_str),
+                    ));
+                }
+            }
+        }
+    }
+
     /**
      * writes the implements of Serialize for the NetStruct
      */
     pub(super) fn serializer(&self) -> Result<TokenStream, DeriveErr> {
         let mut fields_serialize_ts = TokenStream::new();
-        let var = TokenStream::from_str("tup").unwrap();
+        let var = TokenStream::from_str(SERIALIZER_VAR_NAME).unwrap();
+        let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
         let all_vec = self.find_all_vec_fields();
-        for f in self.fields.iter().filter(|f| !f.is_phantom()) {
+        let groups = self.find_all_len_of_groups();
+        for f in self.fields.iter() {
+            for group in groups.iter().filter(|g| g.is_first(f)) {
+                let start = group.start_var();
+                fields_serialize_ts.extend(quote!(let #start = #offset;));
+            }
+            if f.is_phantom() {
+                continue;
+            }
             let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
-            fields_serialize_ts.extend(match &f.ty {
-                NetStructFieldType::Val { ty } => {
-                    quote! {#var.serialize_element::<#ty>(&self.#field_name)?;}
-                },
-                NetStructFieldType::Vec { ty, capacity: _ } => {
-                    let Some(v_f) = all_vec.get(&f.name) else {
-                        return Err(DeriveErr::Message(format!(
-                            "Unexpected error when implementing Serialize for the vector field \"{}\" of the structure \"{}\"", 
-                            &f.name,
-                            self.derive_input.ident.to_string())));
-                    };
-                    let len_field = TokenStream::from_str(v_f.len_field.name.as_str()).unwrap();
-                    let unit = match v_f.len_unit {
-                        SizeUnit::BITS => quote!(as usize / (8_usize * core::mem::size_of::<#ty>())),
-                        SizeUnit::BYTES => quote!(as usize / core::mem::size_of::<#ty>()),
-                        SizeUnit::LENGTH => quote!(as usize),
-                    };
-                    quote! {
-                        for i in self.#field_name[0..(self.#len_field #unit)].iter() {
-                            #var.serialize_element::<#ty>(i)?;
-                        }
-                    }
-                },
-                NetStructFieldType::Arr { ty, capacity: _ } => {
+            let value = match f.const_value() {
+                Some(expr) => quote!((#expr)),
+                None => quote!(self.#field_name),
+            };
+            let mut field_ts = TokenStream::new();
+            if let (Some(condition), true) = (f.condition(), f.is_check_if()) {
+                field_ts.extend(self.check_condition(f, condition));
+            }
+            if let Some(len) = f.reserved() {
+                field_ts.extend(self.serialize_zeros(len));
+            } else if let Some((encode, _)) = f.flavour() {
+                if f.is_vec() {
+                    return Err(DeriveErr::Message(format!(
+                        "The vector field \"{}\" of the structure \"{}\" cannot have a flavour",
+                        &f.name,
+                        self.derive_input.ident.to_string())));
+                }
+                let wire = self.serialize_element(None, quote!(&wire));
+                field_ts.extend(quote! {
+                    let wire = #encode(&#value).map_err(<S::Error as serde::ser::Error>::custom)?;
+                    #wire
+                });
+            } else {
+                field_ts.extend(self.serialize_field_value(f, &value, &all_vec)?);
+            }
+            // the field is omitted if it is not present in the version
+            fields_serialize_ts.extend(match self.version_gate(f) {
+                Some(gate) => {
+                    let deps = self.bind_condition_deps(f);
                     quote! {
-                        for i in self.#field_name.iter() {
-                            #var.serialize_element::<#ty>(i)?;
+                        {
+                            #deps
+                            if #gate {
+                                #field_ts
+                            }
                         }
                     }
                 }
+                None => field_ts,
             });
+            if let Some(pad_to) = f.pad_to() {
+                fields_serialize_ts.extend(self.serialize_padding(pad_to));
+            }
+            fields_serialize_ts.extend(self.serialize_group_end(f, &groups));
+        }
+        if let Some(pad_to) = &self.attrs.pad_to {
+            fields_serialize_ts.extend(self.serialize_padding(pad_to));
         }
 
         let struct_name = &self.derive_input.ident;
         let num_fields = self.fields.len();
+        let init_offset = match self.tracks_offset() {
+            true => quote!(let mut #offset = 0usize;),
+            false => TokenStream::new(),
+        };
         Ok(quote! {
             impl net_struct_serde::traits::Serialize for #struct_name {
                 fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -57,10 +168,152 @@ impl NetStruct {
                 {
                     use serde::ser::SerializeTuple;
                     let mut #var = serializer.serialize_tuple(#num_fields)?;
+                    #init_offset
                     #fields_serialize_ts
                     #var.end()
                 }
             }
         })
     }
+
+    /// serializes an `Option` field in the given encoding
+    fn serialize_option(&self, f: &Rc<NetStructField>, value: &TokenStream, mode: &OptionMode, ty: &TokenStream) -> TokenStream {
+        let field_name_str = f.name.as_str();
+        let invalid = quote! {
+            <S::Error as serde::ser::Error>::custom(net_struct_serde::SerdeErr::InvalidValue(#field_name_str))
+        };
+        let elem = self.serialize_element(Some(ty), quote!(v));
+        match mode {
+            OptionMode::Presence => {
+                let flag = self.serialize_element(Some(&quote!(u8)), quote!(&flag));
+                quote! {
+                    let flag: u8 = match &#value {
+                        Some(_) => 1,
+                        None => 0,
+                    };
+                    #flag
+                    if let Some(v) = &#value {
+                        #elem
+                    }
+                }
+            }
+            OptionMode::Sentinel(sentinel) => {
+                let sentinel = TokenStream::from_str(sentinel.as_str()).unwrap();
+                quote! {
+                    let sentinel: #ty = #sentinel;
+                    let v = match &#value {
+                        Some(v) if *v == sentinel => return Err(#invalid),
+                        Some(v) => v,
+                        None => &sentinel,
+                    };
+                    #elem
+                }
+            }
+            OptionMode::LenPrefix(len_ty) => {
+                let len_ty = TokenStream::from_str(len_ty.as_str()).unwrap();
+                let len = self.serialize_element(Some(&len_ty), quote!(&len));
+                // a value of no bytes would be read back as `None`
+                quote! {
+                    let len = match &#value {
+                        Some(v) => match net_struct_serde::encoded_len(v).map_err(<S::Error as serde::ser::Error>::custom)? {
+                            0 => return Err(#invalid),
+                            len => len,
+                        },
+                        None => 0,
+                    };
+                    let len = <#len_ty as net_struct_serde::traits::LenField>::from_len(len)
+                        .map_err(<S::Error as serde::ser::Error>::custom)?;
+                    #len
+                    if let Some(v) = &#value {
+                        #elem
+                    }
+                }
+            }
+            OptionMode::Trailing => quote! {
+                if let Some(v) = &#value {
+                    #elem
+                }
+            },
+        }
+    }
+
+    fn serialize_field_value(
+        &self,
+        f: &Rc<NetStructField>,
+        value: &TokenStream,
+        all_vec: &HashMap<String, VecField>,
+    ) -> Result<TokenStream, DeriveErr> {
+        if let (Some(mode), Some(ty)) = (f.option_mode(), f.option_inner_ty()) {
+            return Ok(self.serialize_option(f, value, mode, &ty));
+        }
+        if let (Some((terminator, mode)), NetStructFieldType::Arr { ty, .. }) = (f.until(), &f.ty) {
+            let elem = self.serialize_element(Some(ty), quote!(i));
+            let terminator_elem = match mode {
+                TerminatorMode::Peek => TokenStream::new(),
+                _ => self.serialize_element(Some(ty), quote!(&terminator)),
+            };
+            // an included terminator is stored in the array, so there must be room for it
+            let check_room = match mode {
+                TerminatorMode::Include => quote! {
+                    if count == #value.len() {
+                        return Err(<S::Error as serde::ser::Error>::custom(net_struct_serde::SerdeErr::OutOfRange));
+                    }
+                },
+                _ => TokenStream::new(),
+            };
+            return Ok(quote! {
+                let terminator: #ty = #terminator;
+                let count = #value.iter().position(|e| *e == terminator).unwrap_or(#value.len());
+                #check_room
+                for i in #value[0..count].iter() {
+                    #elem
+                }
+                #terminator_elem
+            });
+        }
+        Ok(match &f.ty {
+            NetStructFieldType::Val { ty } => {
+                self.serialize_element(Some(ty), quote!(&#value))
+            },
+            NetStructFieldType::Vec { ty, capacity: _ } => {
+                let Some(v_f) = all_vec.get(&f.name) else {
+                    return Err(DeriveErr::Message(format!(
+                        "Unexpected error when implementing Serialize for the vector field \"{}\" of the structure \"{}\"", 
+                        &f.name,
+                        self.derive_input.ident.to_string())));
+                };
+                let offset = TokenStream::from_str(OFFSET_VAR_NAME).unwrap();
+                let count = self.vec_count(f, v_f, |group| {
+                    let start = group.start_var();
+                    quote!((#offset - #start))
+                });
+                let elems = match is_primitive(ty) {
+                    true => self.serialize_element(None, quote!(&net_struct_serde::traits::PrimitiveSeq::<#ty>(&#value[0..count]))),
+                    false => {
+                        let elem = self.serialize_element(Some(ty), quote!(i));
+                        quote! {
+                            for i in #value[0..count].iter() {
+                                #elem
+                            }
+                        }
+                    }
+                };
+                quote! {
+                    let count = (#count).map_err(<S::Error as serde::ser::Error>::custom)?;
+                    #elems
+                }
+            },
+            NetStructFieldType::Arr { ty, capacity: _ } if is_primitive(ty) => {
+                self.serialize_element(None, quote!(&net_struct_serde::traits::PrimitiveSeq::<#ty>(&#value[..])))
+            }
+            NetStructFieldType::Arr { ty, capacity: _ } => {
+                let elem = self.serialize_element(Some(ty), quote!(i));
+                quote! {
+                    for i in #value.iter() {
+                        #elem
+                    }
+                }
+            }
+        })
+    }
 }
@@ -0,0 +1,315 @@
+use std::str::FromStr;
+
+use super::*;
+use field::NetStructFieldType;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+impl NetStruct {
+    /// the name of the view of the structure, e.g. `FooView`
+    fn view_name(&self) -> TokenStream {
+        TokenStream::from_str(format!("{}View", self.derive_input.ident).as_str()).unwrap()
+    }
+
+    /// the fields that take bytes on the wire
+    fn view_fields(&self) -> Vec<&Rc<NetStructField>> {
+        self.fields.iter().filter(|f| !f.is_phantom()).collect()
+    }
+
+    /// checks that the offset of every field is given by the fixed lengths of the fields before it and by
+    /// the length fields of the vectors before it
+    fn check_view(&self) -> Result<(), DeriveErr> {
+        let struct_name = self.derive_input.ident.to_string();
+        if !self.derive_input.generics.params.is_empty() {
+            return Err(DeriveErr::Message(format!(
+                "The structure \"{}\" with a view cannot be generic",
+                struct_name)));
+        }
+        if self.attrs.pad_to.is_some() {
+            return Err(DeriveErr::Message(format!(
+                "The structure \"{}\" with a view cannot have pad_to",
+                struct_name)));
+        }
+        let all_vec = self.find_all_vec_fields();
+        for (i, f) in self.fields.iter().enumerate() {
+            let supported = f.net_struct_attr.iter().all(|attr| {
+                matches!(
+                    attr,
+                    FieldAttr::Vec { .. }
+                        | FieldAttr::VecLenOf { .. }
+                        | FieldAttr::Phantom
+                        | FieldAttr::Default(_)
+                        | FieldAttr::Expect(_)
+                        | FieldAttr::Range(_)
+                        | FieldAttr::Validate(_)
+                        | FieldAttr::Const(_)
+                        | FieldAttr::Reserved(_)
+                )
+            });
+            if !supported {
+                return Err(DeriveErr::Message(format!(
+                    "The field \"{}\" of the structure \"{}\" with a view can only have the attributes vec_len, field_vec_len, phantom, default, expect, range, validate, const and reserved",
+                    &f.name, struct_name)));
+            }
+            let Some(v_f) = all_vec.get(&f.name) else {
+                continue;
+            };
+            let before = self.fields[..i].iter().any(|l| l.name == v_f.len_field.name);
+            if !before || v_f.len_field.is_phantom() {
+                return Err(DeriveErr::Message(format!(
+                    "The length field \"{}\" of the vector field \"{}\" of the structure \"{}\" with a view must be a field before it",
+                    &v_f.len_field.name, &f.name, struct_name)));
+            }
+        }
+        Ok(())
+    }
+
+    /// the number of bytes of the field `f` in the buffer of the view
+    fn view_field_len(&self, f: &Rc<NetStructField>, all_vec: &HashMap<String, VecField>) -> TokenStream {
+        let field_ty = f.field.ty.to_token_stream();
+        if let Some(len) = f.reserved() {
+            return quote!((#len));
+        }
+        let (NetStructFieldType::Vec { ty, capacity }, Some(v_f)) = (&f.ty, all_vec.get(&f.name)) else {
+            return quote!(<#field_ty as net_struct_serde::traits::FixedLen>::LEN);
+        };
+        let capacity = TokenStream::from_str(capacity.as_str()).unwrap();
+        let len_field = TokenStream::from_str(v_f.len_field.name.as_str()).unwrap();
+        let elem_len = quote!(<#ty as net_struct_serde::traits::FixedLen>::LEN);
+        match v_f.len_unit.is_count() {
+            true => {
+                let count = v_f.len_unit.to_count(&v_f.len_field.name, quote!(#len_field));
+                quote!({
+                    let #len_field = self.#len_field()?;
+                    let count: usize = (#count)?;
+                    if count > #capacity as usize {
+                        return Err(net_struct_serde::SerdeErr::OutOfRange);
+                    }
+                    count * #elem_len
+                })
+            }
+            false => {
+                let bytes = v_f.len_unit.to_bytes(&v_f.len_field.name, quote!(#len_field));
+                quote!({
+                    let #len_field = self.#len_field()?;
+                    let bytes: usize = (#bytes)?;
+                    if bytes > #capacity as usize * #elem_len {
+                        return Err(net_struct_serde::SerdeErr::OutOfRange);
+                    }
+                    if bytes % #elem_len != 0 {
+                        return Err(net_struct_serde::SerdeErr::ParseFailed);
+                    }
+                    bytes
+                })
+            }
+        }
+    }
+
+    /// the getter, and the setter with the mutable slice for a byte array, of the field `f`
+    fn view_accessors(&self, f: &Rc<NetStructField>, range_fn: &TokenStream) -> (TokenStream, TokenStream) {
+        let vis = &f.field.vis;
+        let field_name = TokenStream::from_str(f.name.as_str()).unwrap();
+        let field_ty = f.field.ty.to_token_stream();
+        let setter = TokenStream::from_str(format!("set_{}", f.name).as_str()).unwrap();
+        let get_bytes = quote! {
+            let range = self.#range_fn()?;
+            let bytes = self.buffer.as_ref().get(range).ok_or(net_struct_serde::SerdeErr::Eof)?;
+        };
+        let get_bytes_mut = quote! {
+            let range = self.#range_fn()?;
+            let bytes = self.buffer.as_mut().get_mut(range).ok_or(net_struct_serde::SerdeErr::Eof)?;
+        };
+        let elem_ty = match &f.ty {
+            NetStructFieldType::Arr { ty, .. } | NetStructFieldType::Vec { ty, .. } => Some(ty),
+            NetStructFieldType::Val { .. } => None,
+        };
+        if elem_ty.is_some_and(|ty| ty.to_string() == "u8") {
+            let field_mut = TokenStream::from_str(format!("{}_mut", f.name).as_str()).unwrap();
+            let getter = quote! {
+                #[inline]
+                #vis fn #field_name(&self) -> Result<&[u8], net_struct_serde::SerdeErr> {
+                    #get_bytes
+                    Ok(bytes)
+                }
+            };
+            let setter = quote! {
+                #[inline]
+                #vis fn #field_mut(&mut self) -> Result<&mut [u8], net_struct_serde::SerdeErr> {
+                    #get_bytes_mut
+                    Ok(bytes)
+                }
+
+                /// fails with `SerdeErr::OutOfRange` if the length of `value` is not the length of the field
+                #vis fn #setter(&mut self, value: &[u8]) -> Result<(), net_struct_serde::SerdeErr> {
+                    #get_bytes_mut
+                    if bytes.len() != value.len() {
+                        return Err(net_struct_serde::SerdeErr::OutOfRange);
+                    }
+                    bytes.copy_from_slice(value);
+                    Ok(())
+                }
+            };
+            return (getter, setter);
+        }
+        let check = self.check_field(f);
+        if let (NetStructFieldType::Vec { ty, .. }, true) = (&f.ty, f.is_vec()) {
+            let seq = match is_primitive(ty) {
+                true => quote!(deserialize_primitive_seq),
+                false => quote!(deserialize_seq),
+            };
+            let getter = quote! {
+                #vis fn #field_name(&self) -> Result<#field_ty, net_struct_serde::SerdeErr> {
+                    #get_bytes
+                    let mut #field_name: #field_ty = net_struct_serde::traits::Placeholder::placeholder();
+                    let count = bytes.len() / <#ty as net_struct_serde::traits::FixedLen>::LEN;
+                    let mut deserializer = net_struct_serde::NetStructDeserializer::new(bytes);
+                    net_struct_serde::traits::Deserializer::#seq::<#ty, &mut [#ty]>(&mut deserializer, &mut #field_name, count)?;
+                    #check
+                    Ok(#field_name)
+                }
+            };
+            let write = match is_primitive(ty) {
+                true => quote! {
+                    net_struct_serde::traits::Serialize::serialize(
+                        &net_struct_serde::traits::PrimitiveSeq::<#ty>(value), &mut serializer)
+                },
+                false => quote! {
+                    for e in value {
+                        net_struct_serde::traits::Serialize::serialize(e, &mut serializer)?;
+                    }
+                    Ok(())
+                },
+            };
+            let setter = quote! {
+                /// fails with `SerdeErr::OutOfRange` if the length of `value` is not the length of the field
+                #vis fn #setter(&mut self, value: &[#ty]) -> Result<(), net_struct_serde::SerdeErr> {
+                    #get_bytes_mut
+                    if bytes.len() != value.len() * <#ty as net_struct_serde::traits::FixedLen>::LEN {
+                        return Err(net_struct_serde::SerdeErr::OutOfRange);
+                    }
+                    let mut serializer = net_struct_serde::NetStructSerializer::new(bytes);
+                    #write
+                }
+            };
+            return (getter, setter);
+        }
+        let getter = quote! {
+            #vis fn #field_name(&self) -> Result<#field_ty, net_struct_serde::SerdeErr> {
+                #get_bytes
+                let #field_name: #field_ty = net_struct_serde::from_slice(bytes)?;
+                #check
+                Ok(#field_name)
+            }
+        };
+        let setter = quote! {
+            #vis fn #setter(&mut self, value: #field_ty) -> Result<(), net_struct_serde::SerdeErr> {
+                #get_bytes_mut
+                let mut serializer = net_struct_serde::NetStructSerializer::new(bytes);
+                net_struct_serde::traits::Serialize::serialize(&value, &mut serializer)
+            }
+        };
+        (getter, setter)
+    }
+
+    /**
+     * writes the view of the NetStruct, which reads and writes the fields in place in a buffer
+     */
+    pub(super) fn view(&self) -> Result<TokenStream, DeriveErr> {
+        if !self.attrs.view {
+            return Ok(TokenStream::new());
+        }
+        self.check_view()?;
+        let struct_name = &self.derive_input.ident;
+        let vis = &self.derive_input.vis;
+        let view_name = self.view_name();
+        let doc = format!(
+            "A view of the bytes of [`{}`] in a buffer, whose fields are read and written in place",
+            struct_name
+        );
+        let all_vec = self.find_all_vec_fields();
+        let mut getters = TokenStream::new();
+        let mut setters = TokenStream::new();
+        let mut end = quote!(0usize);
+        for f in self.view_fields() {
+            let field_vis = &f.field.vis;
+            let range_fn = TokenStream::from_str(format!("{}_range", f.name).as_str()).unwrap();
+            let len = self.view_field_len(f, &all_vec);
+            getters.extend(quote! {
+                /// the bytes of the field in the buffer
+                #field_vis fn #range_fn(&self) -> Result<core::ops::Range<usize>, net_struct_serde::SerdeErr> {
+                    let start = #end;
+                    Ok(start..(start + #len))
+                }
+            });
+            end = quote!(self.#range_fn()?.end);
+            if f.reserved().is_some() {
+                continue;
+            }
+            let (getter, setter) = self.view_accessors(f, &range_fn);
+            getters.extend(getter);
+            setters.extend(setter);
+        }
+        Ok(quote! {
+            #[doc = #doc]
+            #[derive(Debug, Clone)]
+            #vis struct #view_name<B: AsRef<[u8]>> {
+                buffer: B,
+            }
+
+            impl<B: AsRef<[u8]>> #view_name<B> {
+                /// the fields are not checked to be in the buffer, the accessors fail with `SerdeErr::Eof` instead
+                #[inline]
+                pub const fn new_unchecked(buffer: B) -> Self {
+                    Self { buffer }
+                }
+
+                /// fails with `SerdeErr::Eof` if the buffer is shorter than the structure
+                pub fn new_checked(buffer: B) -> Result<Self, net_struct_serde::SerdeErr> {
+                    let view = Self::new_unchecked(buffer);
+                    match view.encoded_len()? <= view.buffer.as_ref().len() {
+                        true => Ok(view),
+                        false => Err(net_struct_serde::SerdeErr::Eof),
+                    }
+                }
+
+                #[inline]
+                pub fn into_inner(self) -> B {
+                    self.buffer
+                }
+
+                /// the number of bytes of the structure, given by the length fields in the buffer
+                pub fn encoded_len(&self) -> Result<usize, net_struct_serde::SerdeErr> {
+                    Ok(#end)
+                }
+
+                /// deserializes the whole structure
+                pub fn to_struct(&self) -> Result<#struct_name, net_struct_serde::SerdeErr> {
+                    net_struct_serde::from_slice(self.buffer.as_ref())
+                }
+
+                #getters
+            }
+
+            impl<B: AsRef<[u8]> + AsMut<[u8]>> #view_name<B> {
+                #setters
+            }
+        })
+    }
+
+    /**
+     * writes the implement of FixedLen for the NetStruct with a view or `zerocopy` and without vectors
+     */
+    pub(super) fn fixed_len(&self) -> Result<TokenStream, DeriveErr> {
+        if !(self.attrs.view || self.attrs.zerocopy) || self.fields.iter().any(|f| f.is_vec()) {
+            return Ok(TokenStream::new());
+        }
+        let all_vec = self.find_all_vec_fields();
+        let lens = self.view_fields().into_iter().map(|f| self.view_field_len(f, &all_vec));
+        let struct_name = &self.derive_input.ident;
+        Ok(quote! {
+            impl net_struct_serde::traits::FixedLen for #struct_name {
+                const LEN: usize = 0 #(+ #lens)*;
+            }
+        })
+    }
+}
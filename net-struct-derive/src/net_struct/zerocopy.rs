@@ -0,0 +1,66 @@
+use super::*;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+impl NetStruct {
+    /// whether the fields are laid out in order by `#[repr(C)]` or `#[repr(transparent)]`
+    fn is_repr_c(&self) -> bool {
+        self.derive_input.attrs.iter().any(|attr| match &attr.meta {
+            syn::Meta::List(list) if list.path.is_ident("repr") => list.tokens.clone().into_iter().any(|tt| {
+                matches!(tt, proc_macro2::TokenTree::Ident(i) if i == "C" || i == "transparent")
+            }),
+            _ => false,
+        })
+    }
+
+    /// checks that the wire layout of a `zerocopy` structure can be its memory layout, i.e. the fields are
+    /// in order and take exactly their serialized bytes
+    fn check_zerocopy(&self) -> Result<(), DeriveErr> {
+        let struct_name = self.derive_input.ident.to_string();
+        if !self.is_repr_c() {
+            return Err(DeriveErr::Message(format!(
+                "The zerocopy structure \"{}\" must be declared with #[repr(C)] or #[repr(C, packed)]",
+                struct_name)));
+        }
+        if !self.derive_input.generics.params.is_empty() {
+            return Err(DeriveErr::Message(format!(
+                "The zerocopy structure \"{}\" cannot be generic",
+                struct_name)));
+        }
+        if self.attrs.struct_len.is_some() || self.attrs.pad_to.is_some() || self.attrs.version_field.is_some() {
+            return Err(DeriveErr::Message(format!(
+                "The zerocopy structure \"{}\" cannot have struct_len, pad_to nor version_field",
+                struct_name)));
+        }
+        if let Some(f) = self.fields.iter().find(|f| !f.net_struct_attr.is_empty()) {
+            return Err(DeriveErr::Message(format!(
+                "The field \"{}\" of the zerocopy structure \"{}\" cannot have a {} attribute",
+                &f.name, struct_name, ATTR_PATH)));
+        }
+        Ok(())
+    }
+
+    /**
+     * writes the implements of ZeroCopy for the NetStruct, the types of the fields and the size
+     * of the structure are checked at compile time
+     */
+    pub(super) fn zerocopy(&self) -> Result<TokenStream, DeriveErr> {
+        if !self.attrs.zerocopy {
+            return Ok(TokenStream::new());
+        }
+        self.check_zerocopy()?;
+        let struct_name = &self.derive_input.ident;
+        let field_tys = self.fields.iter().map(|f| f.field.ty.to_token_stream()).collect::<Vec<_>>();
+        Ok(quote! {
+            const _: () = {
+                const fn assert_zerocopy<T: net_struct_serde::traits::ZeroCopy>() {}
+                #(assert_zerocopy::<#field_tys>();)*
+                assert!(core::mem::align_of::<#struct_name>() == 1);
+                assert!(core::mem::size_of::<#struct_name>() == 0 #(+ core::mem::size_of::<#field_tys>())*);
+            };
+            // SAFETY: the fields are `ZeroCopy` and in order without padding, and they are serialized
+            // one after another
+            unsafe impl net_struct_serde::traits::ZeroCopy for #struct_name {}
+        })
+    }
+}
@@ -18,7 +18,7 @@ fn test_unambigious() {
     })
     .unwrap();
     if let syn::Data::Struct(_) = a.data.clone() {
-        let net_struct = NetStruct::from(a);
+        let net_struct = NetStruct::try_from(a).unwrap();
         assert!(net_struct.into().is_err());
     } else {
         panic!()
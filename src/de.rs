@@ -1,6 +1,9 @@
+use core::marker::PhantomData;
+
 use crate::{
-    flavour::NoFlavour,
-    traits::{Deserialize, Deserializer, Flavour, StructDeserializer},
+    traits::{
+        Deserialize, Deserializer, LenField, Placeholder, Primitive, StructDeserializer, Terminator,
+    },
     NetStructDeserializer, SerdeErr,
 };
 
@@ -9,37 +12,36 @@ impl<'a> NetStructDeserializer<'a> {
         Self {
             dir: true,
             init_count: buf.len(),
-            buf: buf,
+            offset: 0,
+            strict_bool: false,
+            version: None,
+            buf,
         }
     }
+
+    /// only accepts `0` or `1` for `bool`, any other value fails with `SerdeErr::ParseFailed`
+    pub fn strict_bool(mut self, strict: bool) -> Self {
+        self.strict_bool = strict;
+        self
+    }
+
+    /// the version of the protocol, for the structures whose `version_field` is a `phantom` field
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = Some(version);
+        self
+    }
 }
 
 macro_rules! deserialize_primty {
     ($s:ident, $primty:ty, $v:ident) => {
-        const SIZE: usize = core::mem::size_of::<$primty>();
-        $s = $s.expect(SIZE)?;
-        let mut arr = core::mem::MaybeUninit::<[u8; SIZE]>::uninit();
-        let buf;
-        unsafe {
-            $s = $s.take(&mut *arr.as_mut_ptr())?;
-            buf = arr.assume_init();
-        }
-        let mut nsd = NetStructDeserializer {
-            dir: true,
-            init_count: SIZE,
-            buf: buf.as_slice(),
-        };
-        *$v = <$primty as Deserialize>::deserialize(&mut nsd)?;
-        return match nsd.finalize() == SIZE {
-            true => Ok($s),
-            false => Err(SerdeErr::ParseFailed),
-        }
+        let mut buf = [0u8; core::mem::size_of::<$primty>()];
+        $s = $s.take(&mut buf)?;
+        *$v = <$primty>::from_be_bytes(buf);
+        return Ok($s);
     };
 }
 
 impl Deserializer for &mut NetStructDeserializer<'_> {
-    type F = NoFlavour<Self>;
-
     #[inline]
     fn expect(self, len: usize) -> Result<Self, SerdeErr> {
         match self.buf.len() >= len {
@@ -61,6 +63,9 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
         };
         b.copy_from_slice(b1);
         self.buf = b2;
+        if self.dir {
+            self.offset += b.len();
+        }
         Ok(self)
     }
 
@@ -70,7 +75,10 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
             true => {
                 self.buf = match self.dir {
                     true => &self.buf[..len],
-                    false => &self.buf[(self.buf.len() - len)..],
+                    false => {
+                        self.offset += self.buf.len() - len;
+                        &self.buf[(self.buf.len() - len)..]
+                    }
                 };
                 Ok(self)
             }
@@ -82,10 +90,12 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
     fn skip(mut self, len: usize) -> Result<Self, SerdeErr> {
         self = self.expect(len)?;
         self.buf = match self.dir {
-            true => &self.buf[len..],
+            true => {
+                self.offset += len;
+                &self.buf[len..]
+            }
             false => &self.buf[..(self.buf.len() - len)],
         };
-        self.buf = &self.buf[len..];
         Ok(self)
     }
 
@@ -95,6 +105,21 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
         Ok(self)
     }
 
+    #[inline]
+    fn offset(&self) -> usize {
+        self.offset
+    }
+
+    #[inline]
+    fn is_reversed(&self) -> bool {
+        !self.dir
+    }
+
+    #[inline]
+    fn version(&self) -> Option<u32> {
+        self.version
+    }
+
     #[inline]
     fn finalize(self) -> usize {
         self.init_count - self.buf.len()
@@ -105,7 +130,10 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
         const SIZE: usize = core::mem::size_of::<bool>();
         let mut arr = [0u8; SIZE];
         self = self.take(&mut arr)?;
-        *v = Self::F::flavour(arr.iter().fold(false, |acc, &i| acc || i != 0u8))?;
+        if self.strict_bool && arr.iter().any(|&i| i > 1u8) {
+            return Err(SerdeErr::ParseFailed);
+        }
+        *v = arr.iter().any(|&i| i != 0u8);
         Ok(self)
     }
 
@@ -169,6 +197,26 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
         Ok(self)
     }
 
+    #[inline]
+    fn deserialize_value<E: Deserialize>(
+        self,
+        _field_name: &'static str,
+    ) -> Result<(Self, E), SerdeErr> {
+        let v = <E as Deserialize>::deserialize(&mut *self)?;
+        Ok((self, v))
+    }
+
+    #[inline]
+    fn deserialize_field_with<W: Deserialize, E>(
+        self,
+        field: &mut E,
+        _field_name: &'static str,
+        decode: impl FnOnce(W) -> Result<E, SerdeErr>,
+    ) -> Result<Self, SerdeErr> {
+        *field = decode(<W as Deserialize>::deserialize(&mut *self)?)?;
+        Ok(self)
+    }
+
     fn deserialize_seq<E: Deserialize, S: AsMut<[E]>>(
         self,
         mut s: S,
@@ -180,39 +228,132 @@ impl Deserializer for &mut NetStructDeserializer<'_> {
         }
         match self.dir {
             true => {
-                for i in 0..len {
-                    arr[i] = E::deserialize(&mut *self)?;
+                for e in arr.iter_mut().take(len) {
+                    *e = E::deserialize(&mut *self)?;
                 }
             }
             false => {
-                for i in (0..len).rev() {
-                    arr[i] = E::deserialize(&mut *self)?;
+                for e in arr.iter_mut().take(len).rev() {
+                    *e = E::deserialize(&mut *self)?;
                 }
             }
         };
         Ok(self)
     }
 
-    fn deserialize_seq_until_end<E: Deserialize, S: AsMut<[E]>>(
+    fn deserialize_primitive_seq<E: Primitive, S: AsMut<[E]>>(
+        mut self,
+        mut s: S,
+        len: usize,
+    ) -> Result<Self, SerdeErr> {
+        let arr = s.as_mut();
+        if arr.len() < len {
+            return Err(SerdeErr::Eof);
+        }
+        let bytes = len * E::SIZE;
+        self = self.expect(bytes)?;
+        // the elements are in order in both directions, as with `deserialize_seq`
+        let (src, rest) = match self.dir {
+            true => (&self.buf[..bytes], &self.buf[bytes..]),
+            false => (
+                &self.buf[(self.buf.len() - bytes)..],
+                &self.buf[..(self.buf.len() - bytes)],
+            ),
+        };
+        match E::as_bytes_mut(&mut arr[..len]) {
+            Some(dst) => dst.copy_from_slice(src),
+            None => {
+                for (e, b) in arr[..len].iter_mut().zip(src.chunks_exact(E::SIZE)) {
+                    *e = E::read_be(b);
+                }
+            }
+        }
+        self.buf = rest;
+        if self.dir {
+            self.offset += bytes;
+        }
+        Ok(self)
+    }
+
+    fn deserialize_seq_bytes<E: Deserialize, S: AsMut<[E]>>(
         self,
         mut s: S,
-        len: &mut usize,
-        len_adj: impl Fn(usize) -> usize,
+        bytes: usize,
+    ) -> Result<(Self, usize), SerdeErr> {
+        if self.buf.len() < bytes {
+            return Err(SerdeErr::Eof);
+        }
+        let end = self.buf.len() - bytes;
+        let arr = s.as_mut();
+        let mut count = 0;
+        while self.buf.len() > end {
+            if count == arr.len() {
+                return Err(SerdeErr::OutOfRange);
+            }
+            arr[count] = E::deserialize(&mut *self)?;
+            count += 1;
+        }
+        if self.buf.len() != end {
+            return Err(SerdeErr::ParseFailed);
+        }
+        if !self.dir {
+            arr[..count].reverse();
+        }
+        Ok((self, count))
+    }
+
+    fn deserialize_seq_until_end<E: Deserialize, S: AsMut<[E]>, L: LenField>(
+        self,
+        mut s: S,
+        len: &mut L,
+        len_adj: impl Fn(usize, usize) -> usize,
     ) -> Result<Self, SerdeErr> {
-        *len = 0;
+        let mut count = 0;
+        let start = self.buf.len();
+        let mut consumed = 0;
         let arr = s.as_mut();
-        while arr.len() >= *len + 1 {
+        while arr.len() > count {
             if let Ok(val) = E::deserialize(&mut *self) {
-                arr[*len] = val;
-                *len += 1;
+                arr[count] = val;
+                count += 1;
+                consumed = start - self.buf.len();
             } else {
                 break;
             }
         }
-        *len = len_adj(*len);
+        *len = L::from_len(len_adj(count, consumed))?;
         Ok(self)
     }
 
+    fn deserialize_seq_until<E: Deserialize + PartialEq, S: AsMut<[E]>>(
+        self,
+        mut s: S,
+        terminator: &E,
+        mode: Terminator,
+    ) -> Result<(Self, usize), SerdeErr> {
+        let arr = s.as_mut();
+        let mut count = 0;
+        loop {
+            let (buf, offset) = (self.buf, self.offset);
+            let e = E::deserialize(&mut *self)?;
+            let is_terminator = e == *terminator;
+            match (is_terminator, mode) {
+                (true, Terminator::Consume) => (),
+                (true, Terminator::Peek) => {
+                    self.buf = buf;
+                    self.offset = offset;
+                }
+                (false, _) | (true, Terminator::Include) => {
+                    *arr.get_mut(count).ok_or(SerdeErr::OutOfRange)? = e;
+                    count += 1;
+                }
+            }
+            if is_terminator {
+                return Ok((self, count));
+            }
+        }
+    }
+
     #[inline]
     fn deserialize_variant<V: Deserialize>(self, v: &mut V) -> Result<Self, SerdeErr> {
         *v = V::deserialize(&mut *self)?;
@@ -247,12 +388,9 @@ macro_rules! impl_deserialize_for_primty {
                 D: Deserializer,
             {
                 const SIZE: usize = core::mem::size_of::<$primty>();
-                unsafe {
-                    let mut arr = core::mem::MaybeUninit::<[u8; SIZE]>::uninit();
-                    let _de = deserializer.take(&mut *arr.as_mut_ptr())?;
-                    let v = <$primty>::from_be_bytes(arr.assume_init());
-                    Ok(D::F::flavour(v)?)
-                }
+                let mut arr = [0u8; SIZE];
+                deserializer.take(&mut arr)?;
+                Ok(<$primty>::from_be_bytes(arr))
             }
         }
     };
@@ -263,16 +401,74 @@ impl_deserialize_for_primty!(i16);
 impl_deserialize_for_primty!(i32);
 impl_deserialize_for_primty!(i64);
 impl_deserialize_for_primty!(i128);
-impl_deserialize_for_primty!(isize);
 impl_deserialize_for_primty!(u8);
 impl_deserialize_for_primty!(u16);
 impl_deserialize_for_primty!(u32);
 impl_deserialize_for_primty!(u64);
 impl_deserialize_for_primty!(u128);
-impl_deserialize_for_primty!(usize);
 impl_deserialize_for_primty!(f32);
 impl_deserialize_for_primty!(f64);
 
+/// `usize` and `isize` are serialized by serde as `u64` and `i64` regardless of the target,
+/// so they are deserialized from 8 bytes as well
+macro_rules! impl_deserialize_for_sizety {
+    ($sizety:ty, $wirety:ty) => {
+        impl Deserialize for $sizety {
+            fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                <$sizety>::try_from(<$wirety as Deserialize>::deserialize(deserializer)?)
+                    .map_err(|_| SerdeErr::OutOfRange)
+            }
+        }
+    };
+}
+
+impl_deserialize_for_sizety!(isize, i64);
+impl_deserialize_for_sizety!(usize, u64);
+
+macro_rules! impl_placeholder_for_primty {
+    ($($primty:ty),*) => {
+        $(
+            impl Placeholder for $primty {
+                #[inline]
+                fn placeholder() -> Self {
+                    Default::default()
+                }
+            }
+        )*
+    };
+}
+
+impl_placeholder_for_primty!(bool, char, i8, i16, i32, i64, i128, isize);
+impl_placeholder_for_primty!(u8, u16, u32, u64, u128, usize, f32, f64);
+
+impl<T: Placeholder, const N: usize> Placeholder for [T; N] {
+    #[inline]
+    fn placeholder() -> Self {
+        core::array::from_fn(|_| T::placeholder())
+    }
+}
+
+impl<T> Placeholder for Option<T> {
+    #[inline]
+    fn placeholder() -> Self {
+        None
+    }
+}
+
+impl Deserialize for bool {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut v = false;
+        deserializer.deserialize_bool(&mut v)?;
+        Ok(v)
+    }
+}
+
 impl<T: Deserialize> Deserialize for Option<T> {
     fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
     where
@@ -285,6 +481,144 @@ impl<T: Deserialize> Deserialize for Option<T> {
     }
 }
 
+impl<T: Deserialize + Placeholder, const N: usize> Deserialize for [T; N] {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut arr = <[T; N]>::placeholder();
+        deserializer.deserialize_seq(&mut arr, N)?;
+        Ok(arr)
+    }
+}
+
+impl Deserialize for char {
+    /// the UTF-8 encoding of the character, i.e. the output of `serialize_char`
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 4];
+        let len = match deserializer.is_reversed() {
+            false => {
+                let mut first = [0u8; 1];
+                let deserializer = deserializer.take(&mut first)?;
+                let len = match first[0] {
+                    0x00..=0x7f => 1,
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0..=0xf7 => 4,
+                    _ => return Err(SerdeErr::ParseFailed),
+                };
+                buf[0] = first[0];
+                deserializer.take(&mut &mut buf[1..len])?;
+                len
+            }
+            true => {
+                // continuation bytes are read from the back until the first byte of the character
+                let mut deserializer = deserializer;
+                let mut len = 0;
+                loop {
+                    let mut byte = [0u8; 1];
+                    deserializer = deserializer.take(&mut byte)?;
+                    buf[3 - len] = byte[0];
+                    len += 1;
+                    match (byte[0] & 0xc0 == 0x80, len) {
+                        (false, _) => break,
+                        (true, 4) => return Err(SerdeErr::ParseFailed),
+                        (true, _) => (),
+                    }
+                }
+                buf.copy_within((4 - len).., 0);
+                len
+            }
+        };
+        core::str::from_utf8(&buf[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .ok_or(SerdeErr::ParseFailed)
+    }
+}
+
+impl Deserialize for () {
+    #[inline]
+    fn deserialize<D>(_deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        Ok(())
+    }
+}
+
+impl<T> Deserialize for PhantomData<T> {
+    #[inline]
+    fn deserialize<D>(_deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        Ok(PhantomData)
+    }
+}
+
+impl Placeholder for () {
+    #[inline]
+    fn placeholder() -> Self {}
+}
+
+impl<T> Placeholder for PhantomData<T> {
+    #[inline]
+    fn placeholder() -> Self {
+        PhantomData
+    }
+}
+
+/// the elements of a tuple are deserialized in order, or in reverse order if the deserializer is reversed,
+/// hence the second list of the elements in reverse order
+macro_rules! impl_deserialize_for_tuple {
+    ($(($ty:ident, $var:ident)),+; $(($rty:ident, $rvar:ident)),+) => {
+        impl<$($ty: Deserialize),+> Deserialize for ($($ty,)+) {
+            fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                let d = deserializer;
+                match d.is_reversed() {
+                    false => {
+                        $(let (d, $var) = d.deserialize_value::<$ty>(stringify!($var))?;)+
+                        let _ = d;
+                        Ok(($($var,)+))
+                    }
+                    true => {
+                        $(let (d, $rvar) = d.deserialize_value::<$rty>(stringify!($rvar))?;)+
+                        let _ = d;
+                        Ok(($($var,)+))
+                    }
+                }
+            }
+        }
+
+        impl<$($ty: Placeholder),+> Placeholder for ($($ty,)+) {
+            #[inline]
+            fn placeholder() -> Self {
+                ($($ty::placeholder(),)+)
+            }
+        }
+    };
+}
+
+impl_deserialize_for_tuple!((T0, t0); (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1); (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2); (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3); (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4); (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5); (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6); (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6), (T7, t7); (T7, t7), (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6), (T7, t7), (T8, t8); (T8, t8), (T7, t7), (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6), (T7, t7), (T8, t8), (T9, t9); (T9, t9), (T8, t8), (T7, t7), (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6), (T7, t7), (T8, t8), (T9, t9), (T10, t10); (T10, t10), (T9, t9), (T8, t8), (T7, t7), (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+impl_deserialize_for_tuple!((T0, t0), (T1, t1), (T2, t2), (T3, t3), (T4, t4), (T5, t5), (T6, t6), (T7, t7), (T8, t8), (T9, t9), (T10, t10), (T11, t11); (T11, t11), (T10, t10), (T9, t9), (T8, t8), (T7, t7), (T6, t6), (T5, t5), (T4, t4), (T3, t3), (T2, t2), (T1, t1), (T0, t0));
+
 #[cfg(test)]
 mod test {
     use super::NetStructDeserializer;
@@ -310,13 +644,11 @@ mod test {
             where
                 D: Deserializer,
             {
-                let mut s = core::mem::MaybeUninit::<St>::uninit();
-                unsafe {
-                    deserializer
-                        .deserialize_i32(&mut (*s.as_mut_ptr()).x)?
-                        .deserialize_i16(&mut (*s.as_mut_ptr()).y)?;
-                    Ok(s.assume_init())
-                }
+                let (mut x, mut y) = (0, 0);
+                deserializer
+                    .deserialize_i32(&mut x)?
+                    .deserialize_i16(&mut y)?;
+                Ok(St { x, y })
             }
         }
         let a: [u8; 6] = [0x00, 0x00, 0x00, 0x01, 0x00, 0x02];
@@ -336,16 +668,11 @@ mod test {
             where
                 D: Deserializer,
             {
-                let mut s = core::mem::MaybeUninit::<St>::uninit();
-                unsafe {
-                    deserializer
-                        .deserialize_i32(&mut (*s.as_mut_ptr()).l)?
-                        .deserialize_seq(
-                            &mut (*s.as_mut_ptr()).arr,
-                            (*s.as_mut_ptr()).l as usize,
-                        )?;
-                    Ok(s.assume_init())
-                }
+                let (mut l, mut arr) = (0, [0; 8]);
+                deserializer
+                    .deserialize_i32(&mut l)?
+                    .deserialize_seq(&mut arr, l as usize)?;
+                Ok(St { l, arr })
             }
         }
         let a: [u8; 6] = [0x00, 0x00, 0x00, 0x02, 0x03, 0x07];
@@ -373,17 +700,16 @@ mod test {
             where
                 D: Deserializer,
             {
-                let mut s = core::mem::MaybeUninit::<St>::uninit();
-                unsafe {
-                    deserializer
-                        .deserialize_u8(&mut (*s.as_mut_ptr()).x)?
-                        .reverse()?
-                        .deserialize_i32(&mut (*s.as_mut_ptr()).l)?
-                        .deserialize_seq(&mut (*s.as_mut_ptr()).arr, (*s.as_mut_ptr()).l as usize)?
-                        .reverse()?
-                        .deserialize_i16(&mut (*s.as_mut_ptr()).y)?;
-                    Ok(s.assume_init())
-                }
+                let (mut x, mut y, mut arr, mut l) = (0, 0, [0; 8], 0);
+                let deserializer = deserializer
+                    .deserialize_u8(&mut x)?
+                    .reverse()?
+                    .deserialize_i32(&mut l)?;
+                deserializer
+                    .deserialize_seq(&mut arr, l as usize)?
+                    .reverse()?
+                    .deserialize_i16(&mut y)?;
+                Ok(St { x, y, arr, l })
             }
         }
         let a = [21, 0, 11, 0x03, 0x05, 0x07, 0x00, 0x00, 0x00, 0x03];
@@ -410,11 +736,9 @@ mod test {
             where
                 D: Deserializer,
             {
-                let mut st = core::mem::MaybeUninit::<St>::uninit();
-                unsafe {
-                    deserializer.deserialize_seq(&mut (*st.as_mut_ptr()).arr, 3)?;
-                    Ok(st.assume_init())
-                }
+                let mut arr = [0; 3];
+                deserializer.deserialize_seq(&mut arr, 3)?;
+                Ok(St { arr })
             }
         }
         let a: [u8; 12] = [
@@ -429,6 +753,103 @@ mod test {
         );
     }
 
+    #[test]
+    fn seq_until1() {
+        let a: [u8; 5] = [3, 4, 0, 7, 0];
+        let mut arr = [0xffu8; 3];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let (d, count) = (&mut nsd)
+            .deserialize_seq_until(&mut arr, &0, Terminator::Consume)
+            .unwrap();
+        assert_eq!((d.offset(), count, arr), (3, 2, [3, 4, 0xff]));
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let (d, count) = (&mut nsd)
+            .deserialize_seq_until(&mut arr, &0, Terminator::Include)
+            .unwrap();
+        assert_eq!((d.offset(), count, arr), (3, 3, [3, 4, 0]));
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let (d, count) = (&mut nsd)
+            .deserialize_seq_until(&mut arr, &0, Terminator::Peek)
+            .unwrap();
+        assert_eq!((d.offset(), count), (2, 2));
+        let mut nsd = NetStructDeserializer::new(&a[..2]);
+        assert_eq!(
+            (&mut nsd)
+                .deserialize_seq_until(&mut arr, &0, Terminator::Consume)
+                .err(),
+            Some(SerdeErr::Eof)
+        );
+        let mut short = [0u8; 1];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        assert_eq!(
+            (&mut nsd)
+                .deserialize_seq_until(&mut short, &0, Terminator::Consume)
+                .err(),
+            Some(SerdeErr::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn compound1() {
+        let a: [u8; 6] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        assert_eq!(
+            <(u16, [u8; 2], ())>::deserialize(&mut nsd),
+            Ok((0x0102, [3, 4], ()))
+        );
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        assert_eq!(
+            <[[u8; 3]; 2]>::deserialize(&mut nsd),
+            Ok([[1, 2, 3], [4, 5, 6]])
+        );
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let d = (&mut nsd).reverse().unwrap();
+        assert_eq!(<(u8, u16)>::deserialize(&mut *d), Ok((4, 0x0506)));
+        assert_eq!(<[u8; 2]>::deserialize(d), Ok([2, 3]));
+    }
+
+    #[test]
+    fn primitive_seq1() {
+        let a: [u8; 7] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07];
+        let mut words = [0u16; 3];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let d = (&mut nsd).deserialize_primitive_seq(&mut words, 2).unwrap();
+        assert_eq!((words, d.offset()), ([0x0102, 0x0304, 0], 4));
+        assert_eq!(
+            d.deserialize_primitive_seq(&mut words, 2).err(),
+            Some(SerdeErr::Eof)
+        );
+        let mut bytes = [0u8; 3];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let d = (&mut nsd).reverse().unwrap();
+        let d = d.deserialize_primitive_seq(&mut bytes, 3).unwrap();
+        assert_eq!(bytes, [5, 6, 7]);
+        d.deserialize_primitive_seq(&mut words, 2).unwrap();
+        assert_eq!(words, [0x0102, 0x0304, 0]);
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let mut seq = [0u16; 3];
+        (&mut nsd).deserialize_seq(&mut seq, 3).unwrap();
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        (&mut nsd).deserialize_primitive_seq(&mut words, 3).unwrap();
+        assert_eq!(words, seq);
+    }
+
+    #[test]
+    fn char1() {
+        let a = "aé€😀".as_bytes();
+        let mut nsd = NetStructDeserializer::new(a);
+        let chars: Result<[char; 4], _> = Deserialize::deserialize(&mut nsd);
+        assert_eq!(chars, Ok(['a', 'é', '€', '😀']));
+        let mut nsd = NetStructDeserializer::new(a);
+        let d = (&mut nsd).reverse().unwrap();
+        assert_eq!(char::deserialize(&mut *d), Ok('😀'));
+        assert_eq!(char::deserialize(&mut *d), Ok('€'));
+        let mut nsd = NetStructDeserializer::new(&a[3..5]);
+        assert_eq!(char::deserialize(&mut nsd), Err(SerdeErr::Eof));
+        let mut nsd = NetStructDeserializer::new(&a[2..]);
+        assert_eq!(char::deserialize(&mut nsd), Err(SerdeErr::ParseFailed));
+    }
+
     #[test]
     fn option1() {
         let a: [u8; 2] = [0x01, 0x02];
@@ -439,10 +860,34 @@ mod test {
         assert_eq!(Option::<u16>::deserialize(&mut nsd), Ok(None));
     }
 
+    #[test]
+    fn skip1() {
+        let a: [u8; 6] = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06];
+        let mut nsd = NetStructDeserializer::new(a.as_slice());
+        let de = (&mut nsd).skip(1).unwrap();
+        assert_eq!(de.offset(), 1);
+        let de = de.reverse().unwrap().skip(2).unwrap().reverse().unwrap();
+        assert_eq!(de.offset(), 1);
+        assert_eq!(u16::deserialize(&mut *de), Ok(0x0203));
+        assert_eq!(de.offset(), 3);
+        assert_eq!(u8::deserialize(&mut *de), Ok(0x04));
+        assert_eq!(u8::deserialize(&mut *de), Err(SerdeErr::Eof));
+        assert_eq!(nsd.finalize(), a.len());
+    }
+
     #[test]
     fn eof1() {
         let a: [u8; 2] = [0x01, 0x02];
         let mut nsd = NetStructDeserializer::new(a.as_slice());
         assert_eq!(u32::deserialize(&mut nsd), Err(SerdeErr::Eof));
     }
+
+    #[test]
+    fn strict_bool1() {
+        let a: [u8; 2] = [0x01, 0x02];
+        assert_eq!(bool::deserialize(&mut NetStructDeserializer::new(&a[1..])), Ok(true));
+        let mut nsd = NetStructDeserializer::new(a.as_slice()).strict_bool(true);
+        assert_eq!(bool::deserialize(&mut nsd), Ok(true));
+        assert_eq!(bool::deserialize(&mut nsd), Err(SerdeErr::ParseFailed));
+    }
 }
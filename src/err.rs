@@ -26,6 +26,10 @@ impl core::fmt::Display for SerdeErr {
             Self::NotSupported => formatter.write_str("not supported"),
             Self::Eof => formatter.write_str("unexpected end of file"),
             Self::ParseFailed => formatter.write_str("failed to deserialize"),
+            Self::OutOfRange => formatter.write_str("value out of range"),
+            Self::InvalidUtf8 => formatter.write_str("invalid UTF-8 string"),
+            Self::NoTerminator => formatter.write_str("missing string terminator"),
+            Self::InvalidValue(field) => write!(formatter, "invalid value for the field \"{}\"", field),
             /* and so forth */
         }
     }
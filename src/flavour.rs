@@ -1,14 +1,158 @@
+//! Ready-made implementations of `Flavour`, to be used with `#[net_struct(flavour = <TYPE>)]`.
+
 use crate::{
-    traits::{Deserializer, Flavour},
+    traits::{Deserialize, Flavour, Serialize},
     SerdeErr,
 };
 
+/// The field is serialized/deserialized as it is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoFlavour;
+
+impl<T: Clone + Serialize + Deserialize> Flavour<T> for NoFlavour {
+    type Wire = T;
+
+    #[inline]
+    fn encode(val: &T) -> Result<T, SerdeErr> {
+        Ok(val.clone())
+    }
+
+    #[inline]
+    fn decode(wire: T) -> Result<T, SerdeErr> {
+        Ok(wire)
+    }
+}
+
+/// Fixed-point number in the Q-format with `FRAC_BITS` fractional bits, stored in the integer `W`,
+/// e.g. `FixedPoint<i16, 8>` is Q8.8. `FRAC_BITS` must be less than 64, which is checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedPoint<W, const FRAC_BITS: u32>(core::marker::PhantomData<W>);
+
+macro_rules! impl_fixed_point {
+    ($wire:ty, $float:ty) => {
+        impl<const FRAC_BITS: u32> Flavour<$float> for FixedPoint<$wire, FRAC_BITS> {
+            type Wire = $wire;
+
+            fn encode(val: &$float) -> Result<$wire, SerdeErr> {
+                const { assert!(FRAC_BITS < 64, "FixedPoint has at most 63 fractional bits") };
+                let scaled = (val * (1u64 << FRAC_BITS) as $float).round();
+                // the bounds are powers of two, which are exact unlike `<$wire>::MAX as $float`
+                let (min, end) = (<$wire>::MIN as $float, 2.0 * (<$wire>::MAX / 2 + 1) as $float);
+                match scaled >= min && scaled < end {
+                    true => Ok(scaled as $wire),
+                    false => Err(SerdeErr::OutOfRange),
+                }
+            }
+
+            #[inline]
+            fn decode(wire: $wire) -> Result<$float, SerdeErr> {
+                const { assert!(FRAC_BITS < 64, "FixedPoint has at most 63 fractional bits") };
+                Ok(wire as $float / (1u64 << FRAC_BITS) as $float)
+            }
+        }
+    };
+    ($wire:ty) => {
+        impl_fixed_point!($wire, f32);
+        impl_fixed_point!($wire, f64);
+    };
+}
+
+impl_fixed_point!(i8);
+impl_fixed_point!(i16);
+impl_fixed_point!(i32);
+impl_fixed_point!(i64);
+impl_fixed_point!(u8);
+impl_fixed_point!(u16);
+impl_fixed_point!(u32);
+impl_fixed_point!(u64);
+
+/// Packed binary-coded decimal, e.g. `1234u16` is stored as `0x1234`
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct NoFlavour<D: Deserializer>(core::marker::PhantomData<D>);
+pub struct Bcd;
+
+macro_rules! impl_bcd {
+    ($primty:ty) => {
+        impl Flavour<$primty> for Bcd {
+            type Wire = $primty;
+
+            fn encode(val: &$primty) -> Result<$primty, SerdeErr> {
+                let (mut val, mut wire) = (*val, 0 as $primty);
+                for nibble in 0..(2 * core::mem::size_of::<$primty>()) {
+                    wire |= (val % 10) << (4 * nibble);
+                    val /= 10;
+                }
+                match val {
+                    0 => Ok(wire),
+                    _ => Err(SerdeErr::OutOfRange),
+                }
+            }
+
+            fn decode(wire: $primty) -> Result<$primty, SerdeErr> {
+                let mut val = 0 as $primty;
+                for nibble in (0..(2 * core::mem::size_of::<$primty>())).rev() {
+                    let digit = (wire >> (4 * nibble)) & 0xf;
+                    if digit > 9 {
+                        return Err(SerdeErr::ParseFailed);
+                    }
+                    val = val * 10 + digit;
+                }
+                Ok(val)
+            }
+        }
+    };
+}
+
+impl_bcd!(u8);
+impl_bcd!(u16);
+impl_bcd!(u32);
+impl_bcd!(u64);
+
+/// Signed integer stored with an offset of half the range, i.e. the sign bit is flipped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OffsetBinary;
+
+macro_rules! impl_offset_binary {
+    ($primty:ty, $wire:ty) => {
+        impl Flavour<$primty> for OffsetBinary {
+            type Wire = $wire;
+
+            #[inline]
+            fn encode(val: &$primty) -> Result<$wire, SerdeErr> {
+                Ok((*val as $wire) ^ (1 << (<$wire>::BITS - 1)))
+            }
+
+            #[inline]
+            fn decode(wire: $wire) -> Result<$primty, SerdeErr> {
+                Ok((wire ^ (1 << (<$wire>::BITS - 1))) as $primty)
+            }
+        }
+    };
+}
+
+impl_offset_binary!(i8, u8);
+impl_offset_binary!(i16, u16);
+impl_offset_binary!(i32, u32);
+impl_offset_binary!(i64, u64);
+
+/// Boolean stored in one byte that must be either `0` or `1`, any other value is rejected
+/// with `SerdeErr::ParseFailed` instead of being read as `true`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StrictBool;
+
+impl Flavour<bool> for StrictBool {
+    type Wire = u8;
+
+    #[inline]
+    fn encode(val: &bool) -> Result<u8, SerdeErr> {
+        Ok(*val as u8)
+    }
 
-impl<D: Deserializer> Flavour<D> for NoFlavour<D> {
     #[inline]
-    fn flavour<T>(val: T) -> Result<T, SerdeErr> {
-        Ok(val)
+    fn decode(wire: u8) -> Result<bool, SerdeErr> {
+        match wire {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SerdeErr::ParseFailed),
+        }
     }
 }
@@ -0,0 +1,235 @@
+//! Half-precision floating-point numbers, e.g. the readings of sensors.
+//! `F16` is the IEEE 754 binary16 format and `Bf16` is the bfloat16 format (the upper half of an `f32`).
+//! They are stored as their bits and converted from/to `f32` with rounding to the nearest, ties to even.
+//! Both of them are also a `Flavour<f32>`, so a `f32` field can be serialized as half-precision with
+//! `#[net_struct(flavour = F16)]`.
+
+use crate::{
+    traits::{Deserialize, Deserializer, FixedLen, Flavour, Placeholder, Serialize, Serializer},
+    SerdeErr,
+};
+
+/// converts `f32` into the bits of a binary16
+fn f32_to_f16_bits(v: f32) -> u16 {
+    let x = v.to_bits();
+    let sign = ((x >> 16) & 0x8000) as u16;
+    let exp = ((x >> 23) & 0xff) as i32;
+    let man = x & 0x7f_ffff;
+    if exp == 0xff {
+        // infinity or NaN, the NaN stays a NaN
+        return match man {
+            0 => sign | 0x7c00,
+            _ => sign | 0x7e00 | (man >> 13) as u16,
+        };
+    }
+    let exp = exp - 127 + 15;
+    if exp >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if exp <= 0 {
+        // subnormal, or zero if it is too small
+        if exp < -10 {
+            return sign;
+        }
+        let man = man | 0x80_0000;
+        let shift = (14 - exp) as u32;
+        let rounded = man + (1 << (shift - 1)) - 1 + ((man >> shift) & 1);
+        return sign | (rounded >> shift) as u16;
+    }
+    // a carry of the mantissa increments the exponent, up to infinity
+    let rounded = man + 0xfff + ((man >> 13) & 1);
+    sign | (((exp as u32) << 10) + (rounded >> 13)) as u16
+}
+
+/// converts the bits of a binary16 into `f32`
+fn f16_bits_to_f32(h: u16) -> f32 {
+    let sign = ((h & 0x8000) as u32) << 16;
+    let exp = ((h >> 10) & 0x1f) as u32;
+    let man = (h & 0x3ff) as u32;
+    match exp {
+        0 => {
+            let v = man as f32 / (1u32 << 24) as f32;
+            match sign {
+                0 => v,
+                _ => -v,
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (man << 13)),
+        _ => f32::from_bits(sign | ((exp + 127 - 15) << 23) | (man << 13)),
+    }
+}
+
+/// converts `f32` into the bits of a bfloat16
+fn f32_to_bf16_bits(v: f32) -> u16 {
+    let x = v.to_bits();
+    match v.is_nan() {
+        true => ((x >> 16) | 0x0040) as u16,
+        false => ((x + 0x7fff + ((x >> 16) & 1)) >> 16) as u16,
+    }
+}
+
+/// converts the bits of a bfloat16 into `f32`
+fn bf16_bits_to_f32(h: u16) -> f32 {
+    f32::from_bits((h as u32) << 16)
+}
+
+macro_rules! impl_half {
+    ($name:ident, $from_f32:ident, $to_f32:ident, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[repr(transparent)]
+        pub struct $name(u16);
+
+        impl $name {
+            #[inline]
+            pub fn from_f32(v: f32) -> Self {
+                Self($from_f32(v))
+            }
+
+            #[inline]
+            pub fn to_f32(self) -> f32 {
+                $to_f32(self.0)
+            }
+
+            /// the bits as they are serialized
+            #[inline]
+            pub const fn to_bits(self) -> u16 {
+                self.0
+            }
+
+            #[inline]
+            pub const fn from_bits(bits: u16) -> Self {
+                Self(bits)
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.to_f32(), f)
+            }
+        }
+
+        impl From<f32> for $name {
+            #[inline]
+            fn from(v: f32) -> Self {
+                Self::from_f32(v)
+            }
+        }
+
+        impl From<$name> for f32 {
+            #[inline]
+            fn from(v: $name) -> f32 {
+                v.to_f32()
+            }
+        }
+
+        impl Serialize for $name {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_u16(self.0)
+            }
+        }
+
+        impl Deserialize for $name {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                Ok(Self(u16::deserialize(deserializer)?))
+            }
+        }
+
+        impl Placeholder for $name {
+            #[inline]
+            fn placeholder() -> Self {
+                Self::default()
+            }
+        }
+
+        impl FixedLen for $name {
+            const LEN: usize = 2;
+        }
+
+        impl Flavour<f32> for $name {
+            type Wire = $name;
+
+            #[inline]
+            fn encode(val: &f32) -> Result<$name, SerdeErr> {
+                Ok(Self::from_f32(*val))
+            }
+
+            #[inline]
+            fn decode(wire: $name) -> Result<f32, SerdeErr> {
+                Ok(wire.to_f32())
+            }
+        }
+    };
+}
+
+impl_half!(
+    F16,
+    f32_to_f16_bits,
+    f16_bits_to_f32,
+    "IEEE 754 half-precision (binary16) floating-point number"
+);
+impl_half!(
+    Bf16,
+    f32_to_bf16_bits,
+    bf16_bits_to_f32,
+    "bfloat16 floating-point number"
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_slice, to_vec};
+
+    #[test]
+    fn f16() {
+        assert_eq!(F16::from_f32(1.0).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(-2.5).to_bits(), 0xc100);
+        assert_eq!(F16::from_f32(65504.0).to_bits(), 0x7bff);
+        assert_eq!(F16::from_f32(65520.0).to_bits(), 0x7c00);
+        assert_eq!(F16::from_f32(f32::NEG_INFINITY).to_bits(), 0xfc00);
+        assert!(F16::from_f32(f32::NAN).to_f32().is_nan());
+        // the smallest subnormal, and a tie that rounds to even
+        assert_eq!(F16::from_f32(5.960_464_5e-8).to_bits(), 0x0001);
+        assert_eq!(F16::from_bits(0x0001).to_f32(), 5.960_464_5e-8);
+        assert_eq!(F16::from_f32(1.0 + 1.0 / 2048.0).to_bits(), 0x3c00);
+        assert_eq!(F16::from_f32(1.0 + 3.0 / 2048.0).to_bits(), 0x3c02);
+        assert_eq!(F16::from_bits(0x3555).to_f32(), 0.333_251_95);
+        let v = F16::from(0.5);
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0x38, 0x00]);
+        assert_eq!(from_slice::<F16>([0x38, 0x00]), Ok(v));
+        assert_eq!(f32::from(v), 0.5);
+    }
+
+    #[test]
+    fn bf16() {
+        assert_eq!(Bf16::from_f32(1.0).to_bits(), 0x3f80);
+        assert_eq!(Bf16::from_f32(-2.5).to_bits(), 0xc020);
+        assert_eq!(
+            Bf16::from_f32(f32::from_bits(0x3f80_8000)).to_bits(),
+            0x3f80
+        );
+        assert_eq!(
+            Bf16::from_f32(f32::from_bits(0x3f81_8000)).to_bits(),
+            0x3f82
+        );
+        assert!(Bf16::from_f32(f32::NAN).to_f32().is_nan());
+        let v = Bf16::from(3.0);
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0x40, 0x40]);
+        assert_eq!(from_slice::<Bf16>([0x40, 0x40]), Ok(v));
+        assert_eq!(v.to_f32(), 3.0);
+    }
+}
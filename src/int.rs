@@ -0,0 +1,291 @@
+//! Integers whose width is not a power of two, e.g. the 24-bit lengths of TLS handshakes,
+//! and the integers of the usual widths stored as bytes, e.g. `U16`, which can be fields of a
+//! `#[net_struct(zerocopy)]` structure.
+//! They are stored as their serialized bytes, `U24` etc. are big-endian and `U24Le` etc. are little-endian.
+//! All of them implement `LenField`, so they can also be used as the length field of
+//! `#[net_struct(vec_len(..))]` and `#[net_struct(struct_len(..))]`.
+
+use crate::{
+    traits::{
+        Deserialize, Deserializer, FixedLen, LenField, Placeholder, Serialize, Serializer, ZeroCopy,
+    },
+    SerdeErr,
+};
+
+macro_rules! impl_int {
+    (@bytes be, $primty:ty, $size:expr, $v:expr) => {{
+        let bytes = $v.to_be_bytes();
+        let mut buf = [0u8; $size];
+        buf.copy_from_slice(&bytes[(bytes.len() - $size)..]);
+        buf
+    }};
+    (@bytes le, $primty:ty, $size:expr, $v:expr) => {{
+        let bytes = $v.to_le_bytes();
+        let mut buf = [0u8; $size];
+        buf.copy_from_slice(&bytes[..$size]);
+        buf
+    }};
+    (@value be, $primty:ty, $size:expr, $bytes:expr) => {{
+        let mut buf = [0u8; core::mem::size_of::<$primty>()];
+        buf[(core::mem::size_of::<$primty>() - $size)..].copy_from_slice(&$bytes);
+        <$primty>::from_be_bytes(buf)
+    }};
+    (@value le, $primty:ty, $size:expr, $bytes:expr) => {{
+        let mut buf = [0u8; core::mem::size_of::<$primty>()];
+        buf[..$size].copy_from_slice(&$bytes);
+        <$primty>::from_le_bytes(buf)
+    }};
+    (@impl $name:ident, $endian:ident, $size:expr, $primty:ty, $doc:expr) => {
+        #[doc = $doc]
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #[repr(transparent)]
+        pub struct $name([u8; $size]);
+
+        impl $name {
+            const SHIFT: u32 = <$primty>::BITS - 8 * $size;
+            pub const BITS: u32 = 8 * $size;
+            pub const MIN: $primty = <$primty>::MIN >> Self::SHIFT;
+            pub const MAX: $primty = <$primty>::MAX >> Self::SHIFT;
+
+            /// the most significant bits that do not fit are discarded
+            #[inline]
+            pub fn new(v: $primty) -> Self {
+                Self(impl_int!(@bytes $endian, $primty, $size, v))
+            }
+
+            #[inline]
+            pub fn get(self) -> $primty {
+                // the shifts sign-extend the signed integers
+                impl_int!(@value $endian, $primty, $size, self.0) << Self::SHIFT >> Self::SHIFT
+            }
+
+            /// the bytes as they are serialized
+            #[inline]
+            pub const fn to_bytes(self) -> [u8; $size] {
+                self.0
+            }
+
+            #[inline]
+            pub const fn from_bytes(bytes: [u8; $size]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Debug::fmt(&self.get(), f)
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(&self.get(), f)
+            }
+        }
+
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                self.get().cmp(&other.get())
+            }
+        }
+
+        impl From<$name> for $primty {
+            #[inline]
+            fn from(v: $name) -> $primty {
+                v.get()
+            }
+        }
+
+        impl TryFrom<$primty> for $name {
+            type Error = SerdeErr;
+
+            #[inline]
+            fn try_from(v: $primty) -> Result<Self, SerdeErr> {
+                match ($name::MIN..=$name::MAX).contains(&v) {
+                    true => Ok(Self::new(v)),
+                    false => Err(SerdeErr::OutOfRange),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        impl Deserialize for $name {
+            #[inline]
+            fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                let mut buf = [0u8; $size];
+                deserializer.take(&mut buf)?;
+                Ok(Self(buf))
+            }
+        }
+
+        impl Placeholder for $name {
+            #[inline]
+            fn placeholder() -> Self {
+                Self::default()
+            }
+        }
+
+        // SAFETY: it is the array of its serialized bytes
+        unsafe impl ZeroCopy for $name {}
+
+        impl FixedLen for $name {
+            const LEN: usize = $size;
+        }
+
+        impl LenField for $name {
+            #[inline]
+            fn to_len(&self) -> Result<usize, SerdeErr> {
+                self.get().to_len()
+            }
+
+            #[inline]
+            fn from_len(len: usize) -> Result<Self, SerdeErr> {
+                <$primty>::try_from(len)
+                    .map_err(|_| SerdeErr::OutOfRange)
+                    .and_then(Self::try_from)
+            }
+        }
+    };
+    ($be:ident, $le:ident, $size:expr, $primty:ty) => {
+        impl_int!(@impl $be, be, $size, $primty, concat!("Big-endian ", stringify!($size), "-byte integer"));
+        impl_int!(@impl $le, le, $size, $primty, concat!("Little-endian ", stringify!($size), "-byte integer"));
+    };
+    ($be:ident, $le:ident, $size:expr, $primty:ty, $wide:ty) => {
+        impl_int!($be, $le, $size, $primty);
+
+        impl From<$be> for $wide {
+            #[inline]
+            fn from(v: $be) -> $wide {
+                v.get() as $wide
+            }
+        }
+
+        impl From<$le> for $wide {
+            #[inline]
+            fn from(v: $le) -> $wide {
+                v.get() as $wide
+            }
+        }
+
+        impl TryFrom<$wide> for $be {
+            type Error = SerdeErr;
+
+            #[inline]
+            fn try_from(v: $wide) -> Result<Self, SerdeErr> {
+                <$primty>::try_from(v)
+                    .map_err(|_| SerdeErr::OutOfRange)
+                    .and_then(Self::try_from)
+            }
+        }
+
+        impl TryFrom<$wide> for $le {
+            type Error = SerdeErr;
+
+            #[inline]
+            fn try_from(v: $wide) -> Result<Self, SerdeErr> {
+                <$primty>::try_from(v)
+                    .map_err(|_| SerdeErr::OutOfRange)
+                    .and_then(Self::try_from)
+            }
+        }
+    };
+}
+
+impl_int!(U16, U16Le, 2, u16);
+impl_int!(I16, I16Le, 2, i16);
+impl_int!(U24, U24Le, 3, u32, u64);
+impl_int!(I24, I24Le, 3, i32, i64);
+impl_int!(U40, U40Le, 5, u64, u128);
+impl_int!(I40, I40Le, 5, i64, i128);
+impl_int!(U48, U48Le, 6, u64, u128);
+impl_int!(I48, I48Le, 6, i64, i128);
+impl_int!(U56, U56Le, 7, u64, u128);
+impl_int!(I56, I56Le, 7, i64, i128);
+impl_int!(U32, U32Le, 4, u32);
+impl_int!(I32, I32Le, 4, i32);
+impl_int!(U64, U64Le, 8, u64);
+impl_int!(I64, I64Le, 8, i64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_slice, to_vec};
+
+    #[test]
+    fn u24() {
+        let v = U24::try_from(0x010203u32).unwrap();
+        assert_eq!(v.get(), 0x010203);
+        assert_eq!(u64::from(v), 0x010203);
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0x01, 0x02, 0x03]);
+        assert_eq!(from_slice::<U24>([0x01, 0x02, 0x03]), Ok(v));
+        let v = U24Le::try_from(0x010203u32).unwrap();
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0x03, 0x02, 0x01]);
+        assert_eq!(from_slice::<U24Le>([0x03, 0x02, 0x01]), Ok(v));
+        assert_eq!(U24::try_from(0x01000000u32), Err(SerdeErr::OutOfRange));
+        assert_eq!(U24::MAX, 0xffffff);
+        assert_eq!(from_slice::<U24>([0x01, 0x02]), Err(SerdeErr::Eof));
+    }
+
+    #[test]
+    fn i24() {
+        let v = I24::try_from(-2i32).unwrap();
+        assert_eq!(v.get(), -2);
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0xff, 0xff, 0xfe]);
+        assert_eq!(
+            from_slice::<I24>([0x80, 0x00, 0x00]).map(I24::get),
+            Ok(I24::MIN)
+        );
+        assert_eq!(
+            from_slice::<I24Le>([0xfe, 0xff, 0xff]).map(I24Le::get),
+            Ok(-2)
+        );
+        assert_eq!(I24::try_from(-0x800001i32), Err(SerdeErr::OutOfRange));
+        assert!(I24::new(-1) < I24::new(1));
+    }
+
+    #[test]
+    fn u48() {
+        let v = U48::try_from(0x0102_0304_0506u64).unwrap();
+        assert_eq!(
+            to_vec::<8, _>(&v).unwrap().as_slice(),
+            [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]
+        );
+        assert_eq!(u128::from(v), 0x0102_0304_0506);
+        assert_eq!(U48::try_from(1u128 << 48), Err(SerdeErr::OutOfRange));
+        assert_eq!(from_slice::<I56Le>([0xff; 7]).map(I56Le::get), Ok(-1));
+        assert_eq!(U40::new(0x12_3456_789a).get(), 0x12_3456_789a);
+    }
+
+    #[test]
+    fn u16() {
+        let v = U16::new(0x0102);
+        assert_eq!(v.to_bytes(), [0x01, 0x02]);
+        assert_eq!(to_vec::<8, _>(&v).unwrap().as_slice(), [0x01, 0x02]);
+        assert_eq!(
+            from_slice::<U16Le>([0x02, 0x01]).map(U16Le::get),
+            Ok(0x0102)
+        );
+        assert_eq!(I16::new(-2).to_bytes(), [0xff, 0xfe]);
+        assert_eq!(I32::from_bytes([0x80, 0, 0, 0]).get(), i32::MIN);
+        assert_eq!(U64::new(u64::MAX).get(), u64::MAX);
+        assert_eq!(U32::MAX, u32::MAX);
+    }
+}
@@ -42,13 +42,194 @@
 //! The \<ARGUMENTS\> are seperated by a comma.
 //! All field attributes are in the form `#[net_struct(<FIELD_ATTR>)]`:
 //! - `vec_len(<VECTOR_LENGTH_FIELD>, <OPTIONAL:LENGTH_UNIT>])`
-//!   - `VECTOR_LENGTH_FIELD`: a field that holds the length of the vector
+//!   - `VECTOR_LENGTH_FIELD`: a field that holds the length of the vector, of any type that implements
+//!     `traits::LenField` (e.g. primitive integers, the odd-width integers in `int` or the
+//!     variable-length integers in `varint`)
 //!   - `LENGTH_UNIT`: length specified in the `VECTOR_LENGTH_FIELD` has a unit:
 //!     - `B` or `bytes`: in Bytes
 //!     - `bits`: in bits
 //!     - `len`: in number of elements (this is also the default if LENGTH_UNIT is not specified)
+//!     - `words(<N>)`: in words of `N` bytes
+//!     - `offset = <EXPR>`: added to the length after the unit is applied, e.g. `words(4), offset = -20`
+//!       for a length in 32-bit words that includes a 20-byte header
+//!     - `expr = <EXPR>`: the length in the unit is given by `EXPR`, where the name of `VECTOR_LENGTH_FIELD`
+//!       refers to its value as `usize`, e.g. `expr = vec1_len * 2` (not supported for a `phantom` length field)
+//!   - a length in bytes or bits is a budget over the encoded sizes of the elements: elements are
+//!     deserialized until exactly that many bytes are consumed, failing with `SerdeErr::ParseFailed` if an
+//!     element crosses the end, and the serializer writes the leading elements that fit exactly (see `encoded_count`)
+//!   - the elements beyond the length are set to `traits::Placeholder::placeholder()` when deserializing
+//! - `field_vec_len(<VECTOR_FIELD>, <OPTIONAL:LENGTH_UNIT>])`
+//!   - the same as `vec_len`, but declared on the length field instead of `VECTOR_FIELD`,
+//!     the length of a vector can only be declared once, which is checked at compile time
+//! ```compile_fail
+//! use net_struct_serde::*;
+//! #[derive(Clone, Copy, Debug, NetStruct)]
+//! pub struct Twice {
+//!     #[net_struct(field_vec_len(data))]
+//!     pub len: u8,
+//!     #[net_struct(vec_len(len))]
+//!     pub data: [u8; 4],
+//! }
+//! ```
+//! ```compile_fail
+//! use net_struct_serde::*;
+//! #[derive(Clone, Copy, Debug, NetStruct)]
+//! pub struct Inconsistent {
+//!     #[net_struct(field_vec_len(data, bytes))]
+//!     pub len: u8,
+//!     #[net_struct(vec_len(len))]
+//!     pub data: [u16; 4],
+//! }
+//! ```
+//! - `len_of(<FIELDS>, <OPTIONAL:LENGTH_UNIT>)`
+//!   - declared on a length field that holds the length of a group of consecutive fields, e.g. a header
+//!     length that includes the header itself
+//!   - `FIELDS`: the names of the fields in the group, or `self` for all the fields of the structure
+//!   - `LENGTH_UNIT`: the same as in `vec_len`, except that the default is `bytes` and `len` is not allowed
+//!   - a vector of the last field of the group with `vec_len(<LENGTH_FIELD>)` takes the rest of the group,
+//!     i.e. the length of the group minus the fields before it
+//!   - if the length field precedes the end of the group, the bytes left at the end of the group are
+//!     skipped when deserializing, and zeros are written when serializing
+//!   - the group must be deserialized forward, and cannot contain a `pad_to` field
+//! - `until = <EXPR>`
+//!   - an array without a length field, whose elements are deserialized until one equal to `EXPR` is read
+//!     (in a structure with a `version_field`, `terminator(..)` must also be given),
+//!     e.g. `until = 0` for a NUL-terminated string, the serializer writes the elements before the first
+//!     `EXPR` and then `EXPR`
+//!   - the elements after the terminator are set to `traits::Placeholder::placeholder()` when deserializing
+//!     and are not compared, the deserializer fails with `SerdeErr::OutOfRange` if no terminator is read
+//!     within the capacity of the array
+//! - `terminator(<MODE>)`
+//!   - what happens to the terminator of an `until` field:
+//!     - `consume`: consumed but not stored in the array (this is also the default)
+//!     - `include`: consumed and stored in the array, so the serializer fails with `SerdeErr::OutOfRange` if
+//!       the array has no room for it
+//!     - `peek`: left for the next field, and not written by the serializer
+//! - `if = <EXPR>`
+//!   - an `Option<T>` field that is only present on the wire if `EXPR` is `true`, where `EXPR` may refer to
+//!     the other fields by their names, e.g. `if = flags & 0x20 != 0` (it can also be given as a string literal)
+//!   - the fields referred to are deserialized first, and the field is deserialized into `None` if `EXPR`
+//!     is `false`, the serializer writes the field if it is `Some`
+//! - `check_if`
+//!   - the serializer fails with `SerdeErr::InvalidValue` if the presence of an `if` field does not agree with
+//!     `EXPR`, where the fields referred to are cloned
+//! - `since = <VERSION>`, `until = <VERSION>`
+//!   - the field is only present in the versions from `since` and up to `until` (inclusive), as given
+//!     by the `version_field` of the structure
+//!   - `until` on an array is rejected as ambiguous, its last version is given by `until_version = <VERSION>`
+//!     (which can also be used for the other fields) and its terminator by adding `terminator(..)`
+//!   - an absent field is set to `Default::default()` (or `default = <EXPR>`) when deserializing, and is neither
+//!     serialized nor compared
+//! ```compile_fail
+//! use net_struct_serde::*;
+//! #[derive(Clone, Copy, Debug, NetStruct)]
+//! #[net_struct(version_field = ver)]
+//! pub struct Hello {
+//!     pub ver: u8,
+//!     #[net_struct(since = 2, until = 3)]
+//!     pub mac: [u8; 6],
+//! }
+//! ```
+//! - `option(<MODE>)`
+//!   - how an `Option<T>` field is encoded:
+//!     - `presence`: a `u8` flag of `0` (`None`) or `1` (`Some`) before the value
+//!     - `sentinel = <EXPR>`: `None` is encoded as `EXPR`, and `Some(EXPR)` cannot be serialized
+//!     - `len_prefix = <TYPE>`: a length field of `TYPE` that holds the encoded length of the value in bytes,
+//!       `None` is a length of `0`, so a `Some` value of no bytes cannot be serialized
+//!     - `trailing`: `Some` if there are bytes remaining, `None` is not serialized, only for the last field
+//!       (this is also how `Option<T>` is deserialized without `option` nor `if`)
 //! - `phantom`
 //!   - a placeholder that will not be serialized, deserialized nor compared
+//!   - the field is set to `Default::default()` when deserializing, unless `default = <EXPR>` is given
+//! - `default = <EXPR>`
+//!   - the value of a `phantom` field after deserialization
+//! - `expect = <EXPR>`
+//!   - the deserialized value must be equal to `EXPR`, otherwise `SerdeErr::InvalidValue` is returned
+//! - `range = <RANGE>`
+//!   - the deserialized value must be contained in `RANGE` (e.g. `1..=5`)
+//! - `validate = <FUNCTION>`
+//!   - the deserialized value is only accepted if `FUNCTION(&value)` returns `true`
+//! - `const = <EXPR>`
+//!   - same as `expect`, but the serializer always writes `EXPR` regardless of the value of the field,
+//!     and the field is not compared
+//! - `flavour = <TYPE>`
+//!   - the field is serialized/deserialized through `<TYPE as traits::Flavour<FIELD_TYPE>>`,
+//!     see the module `flavour` for the ready-made ones
+//! - `with = <MODULE>`
+//!   - same as `flavour`, but through the functions `MODULE::encode(&FIELD_TYPE) -> Result<WIRE_TYPE, SerdeErr>`
+//!     and `MODULE::decode(WIRE_TYPE) -> Result<FIELD_TYPE, SerdeErr>`
+//! - `pad_to(<N>)`
+//!   - zero bytes are inserted after the field, so that the next field starts at a multiple of `N` bytes
+//!     from the start of the structure
+//! - `reserved(<N>)`
+//!   - the field is serialized into `N` zero bytes, which are skipped when deserializing,
+//!     and the field is set to `Default::default()`
+//!
+//! ## Structure Attributes
+//! All structure attributes are in the form `#[net_struct(<STRUCT_ATTR>)]`:
+//! - `struct_len(<STRUCT_LENGTH_FIELD>, <OPTIONAL:LENGTH_UNIT>)`
+//!   - `STRUCT_LENGTH_FIELD`: a field that holds the length of the whole structure
+//!   - `LENGTH_UNIT`: the same as in `vec_len`, except that the default is `bytes` and `len` is not allowed
+//! - `version_field = <FIELD>`
+//!   - the field that holds the version for `since` and `until`, it is deserialized before the versioned fields
+//!   - if it is a `phantom` field, the version is given by `NetStructDeserializer::version` when deserializing
+//!     (or `default = <EXPR>` if it is not given), and by the value of the field when serializing
+//! - `pad_to(<N>)`
+//!   - zero bytes are inserted after the last field, so that the structure is a multiple of `N` bytes long
+//! - `zerocopy`
+//!   - the structure also implements `traits::ZeroCopy`, so it can be viewed in place in a buffer with
+//!     `ref_from_bytes`/`mut_from_bytes` and its bytes taken with `as_bytes`, without copying nor validating
+//!   - the structure must be declared with `#[repr(C)]` (or `#[repr(C, packed)]`), the fields cannot have
+//!     attributes and their types must be `ZeroCopy`, e.g. `u8`, `i8`, arrays of them, the integers of `int`
+//!     (`U16`, `U32`, .. instead of `u16`, `u32`, ..), `string::FixedStr` and other `zerocopy` structures,
+//!     which is checked at compile time
+//! ```
+//! use net_struct_serde::{int::U16, traits::ZeroCopy, *};
+//! #[derive(Clone, Copy, Debug, NetStruct)]
+//! #[net_struct(zerocopy)]
+//! #[repr(C)]
+//! pub struct UdpHeader {
+//!     pub src_port: U16,
+//!     pub dst_port: U16,
+//!     pub len: U16,
+//!     pub checksum: U16,
+//! }
+//! let bytes = [0x13, 0x88, 0x00, 0x35, 0x00, 0x08, 0x00, 0x00];
+//! let header = UdpHeader::ref_from_bytes(&bytes).unwrap();
+//! assert_eq!(header.dst_port.get(), 53);
+//! assert_eq!(header.as_bytes(), bytes);
+//! ```
+//! - `view`
+//!   - a view `<STRUCT>View<B: AsRef<[u8]>>` is also derived, which reads and writes the fields in place in a buffer
+//!     without deserializing the whole structure, e.g. to forward packets after looking at a few fields
+//!   - for every field `<FIELD>` that is not `phantom` nor `reserved`, the view has the getter `<FIELD>()`
+//!     (a slice of the buffer for an array of `u8`), the setter `set_<FIELD>(..)` (and `<FIELD>_mut()` for an array
+//!     of `u8`) if the buffer is `AsMut<[u8]>`, and `<FIELD>_range()` the bytes of the field in the buffer
+//!   - the offset of a field is given by the fields before it, whose types must be `traits::FixedLen`,
+//!     and by the length fields of the vectors before it, which are read from the buffer
+//!   - the getters check `expect`, `range`, `validate` and `const` as when deserializing, the fields cannot have
+//!     the other attributes except `vec_len`, `field_vec_len`, `phantom`, `default` and `reserved`, and the length
+//!     field of a vector must be before it
+//!   - a structure with a view or `zerocopy` and without vectors is also `traits::FixedLen`,
+//!     so it can be a field of another view
+//! ```
+//! use net_struct_serde::*;
+//! #[derive(Clone, Copy, Debug, NetStruct)]
+//! #[net_struct(view)]
+//! pub struct Tlv {
+//!     pub kind: u8,
+//!     pub len: u8,
+//!     #[net_struct(vec_len(len))]
+//!     pub data: [u8; 8],
+//!     pub next: u16,
+//! }
+//! let mut buf = [7, 2, 0xaa, 0xbb, 0x01, 0x02];
+//! let mut view = TlvView::new_checked(&mut buf[..]).unwrap();
+//! assert_eq!(view.data(), Ok(&[0xaa, 0xbb][..]));
+//! assert_eq!(view.next(), Ok(0x0102));
+//! view.set_next(0x0304).unwrap();
+//! assert_eq!(buf[4..], [0x03, 0x04]);
+//! ```
 //!
 //! # NetEnum
 //!
@@ -58,26 +239,95 @@
 //! - `repr(<PRIMITIVE_INTEGER_TYPE>])`
 //!   - `PRIMITIVE_INTEGER_TYPE`: a primitive integer type that the enumeration is serialized/deserialized into/from,
 //!     it is not nesscarily the same type as it is stored in memory (for that, `#[repr(<TYPE_IN_MEMORY>)]` is needed)
+//!   - or a byte array, e.g. `repr([u8; 4])` for FourCC codes, whose values are given by `value`
+//!   - if it is omitted, it is the integer type of `#[repr(<INTEGER_TYPE>)]`
+//!
+//! The variants without a discriminant nor a `value` follow the discriminant of the previous variant,
+//! as in Rust (or `0` for the first variant).
+//! Besides the conversions from/to the repr, the enumeration has `VARIANTS`, `name()`, `from_name()`
+//! and `Display` (the variants with a `range` are not in `VARIANTS` nor given by `from_name`).
+//!
+//! ## Variant Attributes
+//! All variant attributes are in the form `#[net_enum(<VARIANT_ATTR>)]`:
+//! - `value = <VALUE>`
+//!   - the value of the variant in place of its discriminant, e.g. `value = b"RIFF"` for `repr([u8; 4])`
+//! - `alias = <VALUE>, ..`
+//!   - other values that are deserialized into the variant, the discriminant is the value that is serialized
+//! - `range = <START>..=<END>` (or `<START>..<END>`, `<START>..`, `..<END>`)
+//!   - the values of the range are deserialized into the variant, which holds the value, e.g. `Experimental(u8)`
+//!   - serializing a value out of the range fails
+//!
+//! The discriminants, aliases and ranges must not overlap, which is checked at compile time
+//! (ranges are not supported for a byte array `repr`).
+//! ```compile_fail
+//! use net_struct_serde::{traits::*, *};
+//! #[derive(Clone, Copy, Debug, PartialEq, Eq, NetEnum)]
+//! #[net_enum(repr(u8))]
+//! pub enum Opcode {
+//!     Request = 1,
+//!     Reply,
+//!     #[net_enum(alias = 1)]
+//!     Other = 3,
+//! }
+//! ```
+//!
+//! # NetFlags
+//!
+//! A set of flags over the integer of a tuple structure, e.g. the flags of a TCP header.
+//! Unlike `NetEnum`, any combination of the flags is a valid value.
+//! ```
+//! use net_struct_serde::*;
+//! #[derive(Clone, Copy, PartialEq, Eq, NetFlags)]
+//! #[net_flags(FIN = 0x01, SYN = 0x02, RST = 0x04, ACK = 0x10)]
+//! pub struct TcpFlags(u8);
+//! let flags = from_slice::<TcpFlags>([0x52]).unwrap();
+//! assert!(flags.contains(TcpFlags::SYN | TcpFlags::ACK));
+//! assert_eq!(flags.unknown_bits(), 0x40);
+//! assert_eq!(format!("{:?}", flags), "TcpFlags(SYN | ACK | 0x40)");
+//! ```
+//!
+//! ## Structure Attributes
+//! All structure attributes are in the form `#[net_flags(<STRUCT_ATTR>)]`:
+//! - `<NAME> = <VALUE>`
+//!   - a flag, i.e. the constant `NAME` whose bits are `VALUE`
+//! - `strict`
+//!   - the bits that are not a flag are rejected with `SerdeErr::InvalidValue` when deserializing,
+//!     otherwise they are kept
 
 mod de;
 mod err;
 mod ser;
 
-mod flavour;
+pub mod flavour;
+pub mod float;
+pub mod int;
+pub mod net;
+pub mod string;
 pub mod traits;
-pub use net_struct_derive::{NetEnum, NetStruct};
+pub mod varint;
+pub use net_struct_derive::{NetEnum, NetFlags, NetStruct};
 pub use traits::{Deserialize, Deserializer, Serialize, Serializer};
 
 #[derive(Debug)]
 pub struct NetStructSerializer<'a> {
     buf: &'a mut [u8],
     len: usize,
+    dry_run: bool,
 }
 
+/// Serializes the value `.0` with any serializer, and adds the number of bytes it is serialized into
+/// to the counter `.1`. It is used by `NetStruct` to keep track of the offset from the start of the
+/// structure without serializing its fields twice.
+#[doc(hidden)]
+pub struct Counted<'a, T: ?Sized>(pub &'a T, pub &'a core::cell::Cell<usize>);
+
 #[derive(Debug, Clone)]
 pub struct NetStructDeserializer<'a> {
     dir: bool,
     init_count: usize,
+    offset: usize,
+    strict_bool: bool,
+    version: Option<u32>,
     buf: &'a [u8],
 }
 
@@ -88,6 +338,10 @@ pub enum SerdeErr {
     NotEnoughSpace,
     NotSupported,
     ParseFailed,
+    InvalidValue(&'static str),
+    OutOfRange,
+    InvalidUtf8,
+    NoTerminator,
 }
 
 #[cfg(feature = "heapless")]
@@ -131,16 +385,63 @@ where
 {
     use heapless::Vec;
     let mut v = Vec::new();
-    unsafe {
-        v.set_len(N);
-        let mut serializer = NetStructSerializer::new(v.as_mut_slice());
-        value.serialize(&mut serializer)?;
-        let serialized_len = serializer.finalize();
-        v.set_len(serialized_len);
-    }
+    v.resize(N, 0).map_err(|_| SerdeErr::NotEnoughSpace)?;
+    let mut serializer = NetStructSerializer::new(v.as_mut_slice());
+    value.serialize(&mut serializer)?;
+    let serialized_len = serializer.finalize();
+    v.truncate(serialized_len);
     Ok(v)
 }
 
+/// Returns the number of bytes the input is serialized into, without writing them anywhere
+/// ```
+/// use net_struct_serde::*;
+/// #[derive(Debug, NetStruct)]
+/// pub struct StructName {
+///     pub field1: u16,
+///     pub vec1_len: u8,
+///     #[net_struct(vec_len(vec1_len))]
+///     pub vec1: [u32; 8],
+/// }
+/// let s = StructName { field1: 1, vec1_len: 2, vec1: [0; 8] };
+/// assert_eq!(encoded_len(&s), Ok(11));
+/// ```
+pub fn encoded_len<T>(value: &T) -> Result<usize, SerdeErr>
+where
+    T: Serialize + ?Sized,
+{
+    let mut serializer = NetStructSerializer::dry_run();
+    value.serialize(&mut serializer)?;
+    Ok(serializer.finalize())
+}
+
+/// Returns the number of leading elements of `elems` that are serialized into exactly `bytes` bytes,
+/// fails with `SerdeErr::OutOfRange` if there is no such number
+/// ```
+/// use net_struct_serde::*;
+/// assert_eq!(encoded_count(&[1u16, 2, 3], 4), Ok(2));
+/// assert_eq!(encoded_count(&[1u16, 2, 3], 3), Err(SerdeErr::OutOfRange));
+/// ```
+pub fn encoded_count<T>(elems: &[T], bytes: usize) -> Result<usize, SerdeErr>
+where
+    T: Serialize,
+{
+    let mut len = 0;
+    for (i, e) in elems.iter().enumerate() {
+        if len == bytes {
+            return Ok(i);
+        }
+        len += encoded_len(e)?;
+        if len > bytes {
+            return Err(SerdeErr::OutOfRange);
+        }
+    }
+    match len == bytes {
+        true => Ok(elems.len()),
+        false => Err(SerdeErr::OutOfRange),
+    }
+}
+
 #[inline]
 /// Deserialize from the input bytes
 pub fn from_slice<T: Deserialize>(data: impl AsRef<[u8]>) -> Result<T, SerdeErr> {
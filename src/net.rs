@@ -0,0 +1,239 @@
+//! Network addresses. `core::net::Ipv4Addr`, `Ipv6Addr`, `SocketAddrV4` and `SocketAddrV6` are serialized
+//! into their octets (followed by the port for the socket addresses), so they can be used directly as fields.
+//! The flow information and scope id of `SocketAddrV6` are not serialized and are deserialized as `0`.
+
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
+
+use crate::{
+    traits::{Deserialize, Deserializer, FixedLen, Placeholder, Serialize, Serializer},
+    SerdeErr,
+};
+
+/// EUI-48 MAC address
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[repr(transparent)]
+pub struct MacAddr(pub [u8; 6]);
+
+impl MacAddr {
+    pub const BROADCAST: Self = Self([0xff; 6]);
+    pub const UNSPECIFIED: Self = Self([0; 6]);
+
+    #[inline]
+    pub const fn new(a: u8, b: u8, c: u8, d: u8, e: u8, f: u8) -> Self {
+        Self([a, b, c, d, e, f])
+    }
+
+    #[inline]
+    pub const fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    #[inline]
+    pub fn is_broadcast(&self) -> bool {
+        *self == Self::BROADCAST
+    }
+
+    /// the I/G bit is set, broadcast is also multicast
+    #[inline]
+    pub const fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// the U/L bit is set
+    #[inline]
+    pub const fn is_local(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+}
+
+impl From<[u8; 6]> for MacAddr {
+    #[inline]
+    fn from(octets: [u8; 6]) -> Self {
+        Self(octets)
+    }
+}
+
+impl From<MacAddr> for [u8; 6] {
+    #[inline]
+    fn from(addr: MacAddr) -> Self {
+        addr.0
+    }
+}
+
+impl core::fmt::Debug for MacAddr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+impl core::fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl Serialize for MacAddr {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl Deserialize for MacAddr {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 6];
+        deserializer.take(&mut buf)?;
+        Ok(Self(buf))
+    }
+}
+
+impl Deserialize for Ipv4Addr {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 4];
+        deserializer.take(&mut buf)?;
+        Ok(Ipv4Addr::from(buf))
+    }
+}
+
+impl Deserialize for Ipv6Addr {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 16];
+        deserializer.take(&mut buf)?;
+        Ok(Ipv6Addr::from(buf))
+    }
+}
+
+impl Deserialize for SocketAddrV4 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 6];
+        deserializer.take(&mut buf)?;
+        let [a, b, c, d, p0, p1] = buf;
+        Ok(SocketAddrV4::new(
+            Ipv4Addr::new(a, b, c, d),
+            u16::from_be_bytes([p0, p1]),
+        ))
+    }
+}
+
+impl Deserialize for SocketAddrV6 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; 18];
+        deserializer.take(&mut buf)?;
+        let mut ip = [0u8; 16];
+        ip.copy_from_slice(&buf[..16]);
+        Ok(SocketAddrV6::new(
+            Ipv6Addr::from(ip),
+            u16::from_be_bytes([buf[16], buf[17]]),
+            0,
+            0,
+        ))
+    }
+}
+
+impl FixedLen for MacAddr {
+    const LEN: usize = 6;
+}
+
+impl FixedLen for Ipv4Addr {
+    const LEN: usize = 4;
+}
+
+impl FixedLen for Ipv6Addr {
+    const LEN: usize = 16;
+}
+
+impl FixedLen for SocketAddrV4 {
+    const LEN: usize = 6;
+}
+
+impl FixedLen for SocketAddrV6 {
+    const LEN: usize = 18;
+}
+
+impl Placeholder for MacAddr {
+    #[inline]
+    fn placeholder() -> Self {
+        Self::UNSPECIFIED
+    }
+}
+
+impl Placeholder for Ipv4Addr {
+    #[inline]
+    fn placeholder() -> Self {
+        Ipv4Addr::UNSPECIFIED
+    }
+}
+
+impl Placeholder for Ipv6Addr {
+    #[inline]
+    fn placeholder() -> Self {
+        Ipv6Addr::UNSPECIFIED
+    }
+}
+
+impl Placeholder for SocketAddrV4 {
+    #[inline]
+    fn placeholder() -> Self {
+        SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 0)
+    }
+}
+
+impl Placeholder for SocketAddrV6 {
+    #[inline]
+    fn placeholder() -> Self {
+        SocketAddrV6::new(Ipv6Addr::UNSPECIFIED, 0, 0, 0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_slice, to_vec};
+
+    #[test]
+    fn mac_addr() {
+        let mac = MacAddr::new(0x02, 0x00, 0x5e, 0x10, 0x20, 0x30);
+        assert_eq!(
+            to_vec::<8, _>(&mac).unwrap().as_slice(),
+            [0x02, 0x00, 0x5e, 0x10, 0x20, 0x30]
+        );
+        assert_eq!(from_slice::<MacAddr>(mac.octets()), Ok(mac));
+        assert!(mac.is_local() && !mac.is_multicast());
+        assert!(MacAddr::BROADCAST.is_broadcast() && MacAddr::BROADCAST.is_multicast());
+        assert_eq!(format!("{mac}"), "02:00:5e:10:20:30");
+    }
+
+    #[test]
+    fn socket_addr() {
+        let addr = SocketAddrV4::new(Ipv4Addr::new(192, 168, 1, 2), 8080);
+        let serialized = to_vec::<8, _>(&addr).unwrap();
+        assert_eq!(serialized.as_slice(), [192, 168, 1, 2, 0x1f, 0x90]);
+        assert_eq!(from_slice::<SocketAddrV4>(serialized), Ok(addr));
+        let addr = SocketAddrV6::new(Ipv6Addr::LOCALHOST, 443, 0, 0);
+        let serialized = to_vec::<32, _>(&addr).unwrap();
+        assert_eq!(serialized.len(), 18);
+        assert_eq!(from_slice::<SocketAddrV6>(serialized), Ok(addr));
+    }
+}
@@ -1,10 +1,25 @@
+use core::cell::Cell;
+
 use serde::{ser::*, Serialize, Serializer};
 
-use crate::{NetStructSerializer, SerdeErr};
+use crate::{Counted, NetStructSerializer, SerdeErr};
 
 impl<'a> NetStructSerializer<'a> {
     pub fn new(buf: &'a mut [u8]) -> Self {
-        Self { buf, len: 0 }
+        Self {
+            buf,
+            len: 0,
+            dry_run: false,
+        }
+    }
+
+    /// a serializer that only counts the number of bytes serialized
+    pub fn dry_run() -> Self {
+        Self {
+            buf: &mut [],
+            len: 0,
+            dry_run: true,
+        }
     }
 
     pub fn finalize(self) -> usize {
@@ -56,9 +71,18 @@ impl<'a> Serializer for &mut NetStructSerializer<'a> {
         self.serialize_u64(v as u64)
     }
 
+    #[inline]
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_u128(v as u128)
+    }
+
     #[inline]
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
         const SIZE: usize = core::mem::size_of::<u8>();
+        if self.dry_run {
+            self.len += SIZE;
+            return Ok(());
+        }
         match self.buf.len() - self.len >= SIZE {
             true => {
                 self.buf[self.len] = v;
@@ -84,6 +108,11 @@ impl<'a> Serializer for &mut NetStructSerializer<'a> {
         self.serialize_bytes(&v.to_be_bytes())
     }
 
+    #[inline]
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.serialize_bytes(&v.to_be_bytes())
+    }
+
     #[inline]
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
         self.serialize_bytes(&v.to_be_bytes())
@@ -96,7 +125,7 @@ impl<'a> Serializer for &mut NetStructSerializer<'a> {
 
     #[inline]
     fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
-        self.serialize_bytes(v.to_string().as_bytes())
+        self.serialize_bytes(v.encode_utf8(&mut [0u8; 4]).as_bytes())
     }
 
     #[inline]
@@ -105,15 +134,17 @@ impl<'a> Serializer for &mut NetStructSerializer<'a> {
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        match self.buf.len() >= v.len() + self.len {
-            true => {
-                let mut seq = self.serialize_seq(Some(v.len()))?;
-                for b in v {
-                    SerializeSeq::serialize_element(&mut seq, b)?;
-                }
-                SerializeSeq::end(seq)
+        if self.dry_run {
+            self.len += v.len();
+            return Ok(());
+        }
+        match self.buf.get_mut(self.len..(self.len + v.len())) {
+            Some(dst) => {
+                dst.copy_from_slice(v);
+                self.len += v.len();
+                Ok(())
             }
-            false => Err(SerdeErr::NotEnoughSpace),
+            None => Err(SerdeErr::NotEnoughSpace),
         }
     }
 
@@ -231,6 +262,12 @@ impl<'a> Serializer for &mut NetStructSerializer<'a> {
     ) -> Result<Self::SerializeStructVariant, Self::Error> {
         Ok(self)
     }
+
+    /// the compact form is used, e.g. `core::net::Ipv4Addr` is serialized into its 4 octets
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        false
+    }
 }
 
 impl SerializeSeq for &mut NetStructSerializer<'_> {
@@ -373,3 +410,333 @@ impl SerializeStructVariant for &mut NetStructSerializer<'_> {
         Ok(())
     }
 }
+
+/// a serializer that forwards everything to `inner`, and adds the number of bytes that
+/// `NetStructSerializer` would write to `count`
+struct CountingSerializer<'c, S> {
+    inner: S,
+    count: &'c Cell<usize>,
+}
+
+impl<'c, S> CountingSerializer<'c, S> {
+    #[inline]
+    fn add(&self, len: usize) {
+        self.count.set(self.count.get() + len);
+    }
+}
+
+impl<T> serde::Serialize for Counted<'_, T>
+where
+    T: ?Sized + serde::Serialize,
+{
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(CountingSerializer {
+            inner: serializer,
+            count: self.1,
+        })
+    }
+}
+
+macro_rules! count_primty {
+    ($fn:ident, $primty:ty) => {
+        #[inline]
+        fn $fn(self, v: $primty) -> Result<Self::Ok, Self::Error> {
+            self.add(core::mem::size_of::<$primty>());
+            self.inner.$fn(v)
+        }
+    };
+}
+
+impl<'c, S: Serializer> Serializer for CountingSerializer<'c, S> {
+    type Ok = S::Ok;
+
+    type Error = S::Error;
+
+    type SerializeSeq = CountingSerializer<'c, S::SerializeSeq>;
+
+    type SerializeTuple = CountingSerializer<'c, S::SerializeTuple>;
+
+    type SerializeTupleStruct = CountingSerializer<'c, S::SerializeTupleStruct>;
+
+    type SerializeTupleVariant = CountingSerializer<'c, S::SerializeTupleVariant>;
+
+    type SerializeMap = CountingSerializer<'c, S::SerializeMap>;
+
+    type SerializeStruct = CountingSerializer<'c, S::SerializeStruct>;
+
+    type SerializeStructVariant = CountingSerializer<'c, S::SerializeStructVariant>;
+
+    count_primty!(serialize_bool, bool);
+    count_primty!(serialize_i8, i8);
+    count_primty!(serialize_i16, i16);
+    count_primty!(serialize_i32, i32);
+    count_primty!(serialize_i64, i64);
+    count_primty!(serialize_i128, i128);
+    count_primty!(serialize_u8, u8);
+    count_primty!(serialize_u16, u16);
+    count_primty!(serialize_u32, u32);
+    count_primty!(serialize_u64, u64);
+    count_primty!(serialize_u128, u128);
+    count_primty!(serialize_f32, f32);
+    count_primty!(serialize_f64, f64);
+
+    #[inline]
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        self.add(v.len_utf8());
+        self.inner.serialize_char(v)
+    }
+
+    #[inline]
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.add(v.len());
+        self.inner.serialize_str(v)
+    }
+
+    #[inline]
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        self.add(v.len());
+        self.inner.serialize_bytes(v)
+    }
+
+    #[inline]
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.inner.serialize_none()
+    }
+
+    #[inline]
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.inner.serialize_some(&Counted(value, self.count))
+    }
+
+    #[inline]
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        self.inner.serialize_unit()
+    }
+
+    #[inline]
+    fn serialize_unit_struct(self, name: &'static str) -> Result<Self::Ok, Self::Error> {
+        self.inner.serialize_unit_struct(name)
+    }
+
+    #[inline]
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        self.inner
+            .serialize_unit_variant(name, variant_index, variant)
+    }
+
+    #[inline]
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.inner
+            .serialize_newtype_struct(name, &Counted(value, self.count))
+    }
+
+    #[inline]
+    fn serialize_newtype_variant<T>(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.inner.serialize_newtype_variant(
+            name,
+            variant_index,
+            variant,
+            &Counted(value, self.count),
+        )
+    }
+
+    #[inline]
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        self.inner
+            .serialize_seq(len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.inner
+            .serialize_tuple(len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.inner
+            .serialize_tuple_struct(name, len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        self.inner
+            .serialize_tuple_variant(name, variant_index, variant, len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        self.inner
+            .serialize_map(len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        self.inner
+            .serialize_struct(name, len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        self.inner
+            .serialize_struct_variant(name, variant_index, variant, len)
+            .map(|inner| CountingSerializer {
+                inner,
+                count: self.count,
+            })
+    }
+
+    #[inline]
+    fn is_human_readable(&self) -> bool {
+        self.inner.is_human_readable()
+    }
+}
+
+macro_rules! count_compound {
+    ($trait:ident, $fn:ident) => {
+        impl<S: $trait> $trait for CountingSerializer<'_, S> {
+            type Ok = S::Ok;
+
+            type Error = S::Error;
+
+            #[inline]
+            fn $fn<T>(&mut self, value: &T) -> Result<(), Self::Error>
+            where
+                T: ?Sized + serde::Serialize,
+            {
+                self.inner.$fn(&Counted(value, self.count))
+            }
+
+            #[inline]
+            fn end(self) -> Result<Self::Ok, Self::Error> {
+                self.inner.end()
+            }
+        }
+    };
+    ($trait:ident, $fn:ident, key) => {
+        impl<S: $trait> $trait for CountingSerializer<'_, S> {
+            type Ok = S::Ok;
+
+            type Error = S::Error;
+
+            #[inline]
+            fn $fn<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+            where
+                T: ?Sized + serde::Serialize,
+            {
+                self.inner.$fn(key, &Counted(value, self.count))
+            }
+
+            #[inline]
+            fn end(self) -> Result<Self::Ok, Self::Error> {
+                self.inner.end()
+            }
+        }
+    };
+}
+
+count_compound!(SerializeSeq, serialize_element);
+count_compound!(SerializeTuple, serialize_element);
+count_compound!(SerializeTupleStruct, serialize_field);
+count_compound!(SerializeTupleVariant, serialize_field);
+count_compound!(SerializeStruct, serialize_field, key);
+count_compound!(SerializeStructVariant, serialize_field, key);
+
+impl<S: SerializeMap> SerializeMap for CountingSerializer<'_, S> {
+    type Ok = S::Ok;
+
+    type Error = S::Error;
+
+    #[inline]
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.inner.serialize_key(&Counted(key, self.count))
+    }
+
+    #[inline]
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + serde::Serialize,
+    {
+        self.inner.serialize_value(&Counted(value, self.count))
+    }
+
+    #[inline]
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        self.inner.end()
+    }
+}
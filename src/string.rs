@@ -0,0 +1,322 @@
+//! Strings of protocols, e.g. device names, firmware versions and FourCC codes.
+//! `FixedStr<N>` always takes `N` bytes, the string being padded with the `PAD` byte,
+//! and `CStr<N>` is terminated by a NUL byte and takes at most `N` bytes, the NUL included.
+//! Both of them are validated as UTF-8, or as ASCII for `FixedStr<N, PAD, true>`,
+//! and fail with `SerdeErr::InvalidUtf8` otherwise.
+
+use serde::ser::SerializeTuple;
+
+use crate::{
+    traits::{Deserialize, Deserializer, FixedLen, Placeholder, Serialize, Serializer, ZeroCopy},
+    SerdeErr,
+};
+
+/// A string of exactly `N` bytes, padded with the trailing `PAD` bytes, e.g. `FixedStr<4, b' '>`.
+/// The trailing `PAD` bytes are not part of the string.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct FixedStr<const N: usize, const PAD: u8 = 0, const ASCII: bool = false>([u8; N]);
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> FixedStr<N, PAD, ASCII> {
+    /// fails with `SerdeErr::OutOfRange` if `s` is longer than `N` bytes
+    pub fn new(s: &str) -> Result<Self, SerdeErr> {
+        if s.len() > N {
+            return Err(SerdeErr::OutOfRange);
+        }
+        if ASCII && !s.is_ascii() {
+            return Err(SerdeErr::InvalidUtf8);
+        }
+        let mut buf = [PAD; N];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(Self(buf))
+    }
+
+    /// the bytes as they are serialized
+    #[inline]
+    pub const fn to_bytes(self) -> [u8; N] {
+        self.0
+    }
+
+    pub fn from_bytes(bytes: [u8; N]) -> Result<Self, SerdeErr> {
+        let s = Self(bytes);
+        match s.validate() {
+            true => Ok(s),
+            false => Err(SerdeErr::InvalidUtf8),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(self.trimmed()).unwrap_or_default()
+    }
+
+    fn trimmed(&self) -> &[u8] {
+        let len = self.0.iter().rposition(|b| *b != PAD).map_or(0, |i| i + 1);
+        &self.0[..len]
+    }
+
+    fn validate(&self) -> bool {
+        match ASCII {
+            true => self.trimmed().is_ascii(),
+            false => core::str::from_utf8(self.trimmed()).is_ok(),
+        }
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> Default for FixedStr<N, PAD, ASCII> {
+    #[inline]
+    fn default() -> Self {
+        Self([PAD; N])
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> TryFrom<&str> for FixedStr<N, PAD, ASCII> {
+    type Error = SerdeErr;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<Self, SerdeErr> {
+        Self::new(s)
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> PartialEq<&str> for FixedStr<N, PAD, ASCII> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> core::fmt::Debug
+    for FixedStr<N, PAD, ASCII>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> core::fmt::Display
+    for FixedStr<N, PAD, ASCII>
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> Serialize for FixedStr<N, PAD, ASCII> {
+    #[inline]
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> Deserialize for FixedStr<N, PAD, ASCII> {
+    #[inline]
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let mut buf = [0u8; N];
+        deserializer.take(&mut buf)?;
+        Self::from_bytes(buf)
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> Placeholder for FixedStr<N, PAD, ASCII> {
+    #[inline]
+    fn placeholder() -> Self {
+        Self::default()
+    }
+}
+
+impl<const N: usize, const PAD: u8, const ASCII: bool> FixedLen for FixedStr<N, PAD, ASCII> {
+    const LEN: usize = N;
+}
+
+// SAFETY: it is the array of its serialized bytes, and the bytes that are not a valid string are
+// not read as a `str`
+unsafe impl<const N: usize, const PAD: u8, const ASCII: bool> ZeroCopy for FixedStr<N, PAD, ASCII> {}
+
+/// A NUL-terminated string of at most `N` bytes including the NUL, i.e. at most `N - 1` bytes of string.
+/// Deserializing fails with `SerdeErr::NoTerminator` if there is no NUL within `N` bytes,
+/// and it cannot be deserialized in reverse.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CStr<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> CStr<N> {
+    /// fails with `SerdeErr::OutOfRange` if `s` and the NUL do not fit in `N` bytes,
+    /// or with `SerdeErr::InvalidValue` if `s` contains a NUL
+    pub fn new(s: &str) -> Result<Self, SerdeErr> {
+        if s.len() >= N {
+            return Err(SerdeErr::OutOfRange);
+        }
+        if s.as_bytes().contains(&0) {
+            return Err(SerdeErr::InvalidValue("CStr"));
+        }
+        let mut buf = [0u8; N];
+        buf[..s.len()].copy_from_slice(s.as_bytes());
+        Ok(Self { buf, len: s.len() })
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.buf[..self.len]).unwrap_or_default()
+    }
+
+    /// the number of bytes of the string, the NUL excluded
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<const N: usize> Default for CStr<N> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            buf: [0u8; N],
+            len: 0,
+        }
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for CStr<N> {
+    type Error = SerdeErr;
+
+    #[inline]
+    fn try_from(s: &str) -> Result<Self, SerdeErr> {
+        Self::new(s)
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for CStr<N> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for CStr<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl<const N: usize> core::fmt::Display for CStr<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> Serialize for CStr<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tup = serializer.serialize_tuple(self.len + 1)?;
+        for b in &self.buf[..self.len] {
+            tup.serialize_element(b)?;
+        }
+        tup.serialize_element(&0u8)?;
+        tup.end()
+    }
+}
+
+impl<const N: usize> Deserialize for CStr<N> {
+    fn deserialize<D>(mut deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        if deserializer.is_reversed() {
+            return Err(SerdeErr::NotSupported);
+        }
+        let mut buf = [0u8; N];
+        for len in 0..N {
+            let mut byte = [0u8; 1];
+            deserializer = deserializer.take(&mut byte)?;
+            if byte[0] == 0 {
+                core::str::from_utf8(&buf[..len]).map_err(|_| SerdeErr::InvalidUtf8)?;
+                return Ok(Self { buf, len });
+            }
+            buf[len] = byte[0];
+        }
+        Err(SerdeErr::NoTerminator)
+    }
+}
+
+impl<const N: usize> Placeholder for CStr<N> {
+    #[inline]
+    fn placeholder() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_slice, to_vec};
+
+    #[test]
+    fn fixed_str() {
+        let v = FixedStr::<8>::new("eth0").unwrap();
+        assert_eq!(v, "eth0");
+        assert_eq!(
+            to_vec::<16, _>(&v).unwrap().as_slice(),
+            [b'e', b't', b'h', b'0', 0, 0, 0, 0]
+        );
+        assert_eq!(from_slice::<FixedStr<8>>(*b"eth0\0\0\0\0"), Ok(v));
+        assert_eq!(FixedStr::<2>::new("eth0"), Err(SerdeErr::OutOfRange));
+        assert_eq!(
+            from_slice::<FixedStr<4>>([0xc3, 0x28, 0, 0]),
+            Err(SerdeErr::InvalidUtf8)
+        );
+        assert_eq!(from_slice::<FixedStr<4>>(*b"ab"), Err(SerdeErr::Eof));
+        let v = FixedStr::<6, b' ', true>::new("v1.2").unwrap();
+        assert_eq!(v.to_bytes(), *b"v1.2  ");
+        assert_eq!(v.as_str(), "v1.2");
+        assert_eq!(format!("{}", v), "v1.2");
+        assert_eq!(
+            FixedStr::<6, b' ', true>::new("é"),
+            Err(SerdeErr::InvalidUtf8)
+        );
+        assert_eq!(
+            from_slice::<FixedStr<4, b' ', true>>(*b"\xc3\xa9  "),
+            Err(SerdeErr::InvalidUtf8)
+        );
+        assert_eq!(
+            from_slice::<FixedStr<4>>(*b"\xc3\xa9\0\0"),
+            Ok(FixedStr::new("é").unwrap())
+        );
+    }
+
+    #[test]
+    fn cstr() {
+        let v = CStr::<8>::new("abc").unwrap();
+        assert_eq!((v.len(), v.as_str()), (3, "abc"));
+        assert_eq!(to_vec::<16, _>(&v).unwrap().as_slice(), *b"abc\0");
+        assert_eq!(from_slice::<CStr<8>>(*b"abc\0xyz"), Ok(v));
+        assert_eq!(
+            from_slice::<CStr<4>>(*b"abc\0"),
+            Ok(v.as_str().try_into().unwrap())
+        );
+        assert_eq!(CStr::<3>::new("abc"), Err(SerdeErr::OutOfRange));
+        assert_eq!(
+            from_slice::<CStr<3>>(*b"abc\0"),
+            Err(SerdeErr::NoTerminator)
+        );
+        assert_eq!(from_slice::<CStr<8>>(*b"abc"), Err(SerdeErr::Eof));
+        assert_eq!(
+            from_slice::<CStr<8>>(*b"\xff\0"),
+            Err(SerdeErr::InvalidUtf8)
+        );
+        assert!(from_slice::<CStr<1>>([0]).unwrap().is_empty());
+    }
+}
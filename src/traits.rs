@@ -2,9 +2,34 @@ pub use serde::{Serialize, Serializer};
 
 use crate::SerdeErr;
 
-/// A mapping from a deserialized value to a valid value
-pub trait Flavour<D: Deserializer>: Sized {
-    fn flavour<T>(val: T) -> Result<T, SerdeErr>;
+/// A mapping between the value of a field and its representation on the wire,
+/// used by the field attribute `#[net_struct(flavour = <TYPE>)]`. For example:
+/// ```
+/// use net_struct_serde::{traits::Flavour, *};
+/// pub struct Centi;
+/// impl Flavour<f32> for Centi {
+///     type Wire = u16;
+///     fn encode(val: &f32) -> Result<u16, SerdeErr> {
+///         Ok((val * 100.0) as u16)
+///     }
+///     fn decode(wire: u16) -> Result<f32, SerdeErr> {
+///         Ok(wire as f32 / 100.0)
+///     }
+/// }
+/// #[derive(Debug, NetStruct)]
+/// pub struct SomeStruct {
+///     #[net_struct(flavour = Centi)]
+///     pub voltage: f32,
+/// }
+/// assert_eq!(from_slice::<SomeStruct>([0x01, 0x2c]).unwrap().voltage, 3.0);
+/// ```
+pub trait Flavour<T> {
+    /// the type that is serialized/deserialized in place of `T`
+    type Wire: Serialize + Deserialize;
+
+    fn encode(val: &T) -> Result<Self::Wire, SerdeErr>;
+
+    fn decode(wire: Self::Wire) -> Result<T, SerdeErr>;
 }
 
 /// The `net_struct_serde::traits::Deserializer` is different from `serde::Deserializer`
@@ -21,8 +46,6 @@ pub trait Flavour<D: Deserializer>: Sized {
 /// }
 /// ```
 pub trait Deserializer: Sized {
-    type F: Flavour<Self>;
-
     /// check if there is at least `len` bytes ahead
     fn expect(self, len: usize) -> Result<Self, SerdeErr>;
 
@@ -38,6 +61,15 @@ pub trait Deserializer: Sized {
     /// keep only `len` bytes
     fn truncate(self, len: usize) -> Result<Self, SerdeErr>;
 
+    /// returns the number of bytes consumed from the front of the buffer
+    fn offset(&self) -> usize;
+
+    /// returns `true` if the deserializer reads from the back of the buffer
+    fn is_reversed(&self) -> bool;
+
+    /// returns the version of the protocol given to the deserializer, if any
+    fn version(&self) -> Option<u32>;
+
     /// returns the number of bytes deserialized
     fn finalize(self) -> usize;
 
@@ -69,22 +101,198 @@ pub trait Deserializer: Sized {
         field_name: &'static str,
     ) -> Result<Self, SerdeErr>;
 
+    /// deserialize a field by value, returned together with the deserializer
+    fn deserialize_value<F: Deserialize>(self, field_name: &'static str)
+        -> Result<(Self, F), SerdeErr>;
+
+    /// deserialize the wire representation `W` of a field and map it into the field with `decode`
+    fn deserialize_field_with<W: Deserialize, F>(
+        self,
+        field: &mut F,
+        field_name: &'static str,
+        decode: impl FnOnce(W) -> Result<F, SerdeErr>,
+    ) -> Result<Self, SerdeErr>;
+
     fn deserialize_seq<E: Deserialize, S: AsMut<[E]>>(
         self,
         s: S,
         len: usize,
     ) -> Result<Self, SerdeErr>;
 
-    fn deserialize_seq_until_end<E: Deserialize, S: AsMut<[E]>>(
+    /// deserialize `len` primitives at once, the same as `deserialize_seq` but with a single bounds check
+    fn deserialize_primitive_seq<E: Primitive, S: AsMut<[E]>>(
+        self,
+        s: S,
+        len: usize,
+    ) -> Result<Self, SerdeErr>;
+
+    /// deserialize elements until exactly `bytes` bytes are consumed, returns the number of elements,
+    /// fails with `SerdeErr::ParseFailed` if an element does not end at the boundary, and with
+    /// `SerdeErr::OutOfRange` if the bytes hold more elements than `s`
+    fn deserialize_seq_bytes<E: Deserialize, S: AsMut<[E]>>(
+        self,
+        s: S,
+        bytes: usize,
+    ) -> Result<(Self, usize), SerdeErr>;
+
+    /// deserialize elements until the end, `len_adj` maps the number of elements and the number of bytes
+    /// consumed into the value of `len`
+    fn deserialize_seq_until_end<E: Deserialize, S: AsMut<[E]>, L: LenField>(
         self,
         s: S,
-        len: &mut usize,
-        len_adj: impl Fn(usize) -> usize,
+        len: &mut L,
+        len_adj: impl Fn(usize, usize) -> usize,
     ) -> Result<Self, SerdeErr>;
 
+    /// deserialize elements until one equal to `terminator` is read, returns the number of elements stored,
+    /// fails with `SerdeErr::OutOfRange` if there are more elements to store than `s` holds
+    fn deserialize_seq_until<E: Deserialize + PartialEq, S: AsMut<[E]>>(
+        self,
+        s: S,
+        terminator: &E,
+        mode: Terminator,
+    ) -> Result<(Self, usize), SerdeErr>;
+
     fn deserialize_variant<V: Deserialize>(self, variant: &mut V) -> Result<Self, SerdeErr>;
 }
 
+/// What happens to the terminator of a sequence, i.e. the field attribute `#[net_struct(terminator(..))]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Terminator {
+    /// the terminator is consumed but not stored
+    #[default]
+    Consume,
+    /// the terminator is consumed and stored as the last element
+    Include,
+    /// the terminator is left for the next field
+    Peek,
+}
+
+/// A primitive integer or float, serialized into its big-endian bytes.
+/// The arrays and vectors of primitives are serialized/deserialized in bulk rather than element by element.
+pub trait Primitive: Copy + Serialize + Deserialize {
+    const SIZE: usize;
+
+    /// writes the big-endian bytes into `out` of `SIZE` bytes
+    fn write_be(self, out: &mut [u8]);
+
+    /// reads the big-endian bytes from `bytes` of `SIZE` bytes
+    fn read_be(bytes: &[u8]) -> Self;
+
+    /// the bytes of the elements if they are the same as their serialized bytes, i.e. for `u8`
+    #[inline]
+    fn as_bytes(_elems: &[Self]) -> Option<&[u8]> {
+        None
+    }
+
+    /// the mutable bytes of the elements if they are the same as their serialized bytes, i.e. for `u8`
+    #[inline]
+    fn as_bytes_mut(_elems: &mut [Self]) -> Option<&mut [u8]> {
+        None
+    }
+}
+
+/// Serializes a slice of primitives in bulk, with one `serialize_bytes` for `u8`
+/// and one for every chunk of 256 bytes otherwise
+pub struct PrimitiveSeq<'a, T: Primitive>(pub &'a [T]);
+
+impl<T: Primitive> Serialize for PrimitiveSeq<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        use serde::ser::SerializeTuple;
+        struct Bytes<'a>(&'a [u8]);
+        impl Serialize for Bytes<'_> {
+            #[inline]
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_bytes(self.0)
+            }
+        }
+        if let Some(bytes) = T::as_bytes(self.0) {
+            return serializer.serialize_bytes(bytes);
+        }
+        const CHUNK: usize = 256;
+        let mut tup = serializer.serialize_tuple(self.0.len())?;
+        let mut buf = [0u8; CHUNK];
+        for elems in self.0.chunks(CHUNK / T::SIZE) {
+            let len = elems.len() * T::SIZE;
+            for (e, out) in elems.iter().zip(buf[..len].chunks_exact_mut(T::SIZE)) {
+                e.write_be(out);
+            }
+            tup.serialize_element(&Bytes(&buf[..len]))?;
+        }
+        tup.end()
+    }
+}
+
+macro_rules! impl_primitive_for_primty {
+    ($($primty:ty),*) => {
+        $(
+            impl Primitive for $primty {
+                const SIZE: usize = core::mem::size_of::<$primty>();
+
+                #[inline]
+                fn write_be(self, out: &mut [u8]) {
+                    out.copy_from_slice(&self.to_be_bytes());
+                }
+
+                #[inline]
+                fn read_be(bytes: &[u8]) -> Self {
+                    let mut arr = [0u8; core::mem::size_of::<$primty>()];
+                    arr.copy_from_slice(bytes);
+                    <$primty>::from_be_bytes(arr)
+                }
+            }
+        )*
+    };
+}
+
+impl_primitive_for_primty!(i8, i16, i32, i64, i128, u16, u32, u64, u128, f32, f64);
+
+impl Primitive for u8 {
+    const SIZE: usize = 1;
+
+    #[inline]
+    fn write_be(self, out: &mut [u8]) {
+        out[0] = self;
+    }
+
+    #[inline]
+    fn read_be(bytes: &[u8]) -> Self {
+        bytes[0]
+    }
+
+    #[inline]
+    fn as_bytes(elems: &[u8]) -> Option<&[u8]> {
+        Some(elems)
+    }
+
+    #[inline]
+    fn as_bytes_mut(elems: &mut [u8]) -> Option<&mut [u8]> {
+        Some(elems)
+    }
+}
+
+/// A field that holds a length, i.e. the `VECTOR_LENGTH_FIELD` of `#[net_struct(vec_len(..))]`
+pub trait LenField: Sized {
+    /// fails with `SerdeErr::OutOfRange` if the value is not a valid length, e.g. negative
+    fn to_len(&self) -> Result<usize, SerdeErr>;
+
+    fn from_len(len: usize) -> Result<Self, SerdeErr>;
+}
+
+/// The value of the elements of a vector field beyond its length, e.g. `0` for integers,
+/// so that a deserialized structure is always fully initialised.
+/// It is derived by `NetStruct` and `NetEnum` (the first variant), and is needed for the element types
+/// of array and vector fields.
+pub trait Placeholder {
+    fn placeholder() -> Self;
+}
+
 pub trait StructDeserializer<D: Deserializer>: Sized {
     fn deserialize_field<F: Deserialize>(
         self,
@@ -110,3 +318,124 @@ pub trait NetEnum:
     Clone + crate::traits::Serialize + crate::traits::Deserialize + core::cmp::Eq
 {
 }
+
+/// A set of flags over an integer, derived by `NetFlags`.
+/// `bits` and `from_bits_retain` convert it from/to the integer, e.g. to pack it with other bits.
+pub trait NetFlags:
+    Copy + crate::traits::Serialize + crate::traits::Deserialize + core::cmp::Eq
+{
+    type Bits;
+
+    fn bits(&self) -> Self::Bits;
+
+    /// the bits that are not a named flag are kept
+    fn from_bits_retain(bits: Self::Bits) -> Self;
+}
+
+/// A type that is always serialized into `LEN` bytes, so the offsets of the fields of the views of
+/// `#[net_struct(view)]` are known without deserializing them
+pub trait FixedLen {
+    const LEN: usize;
+}
+
+macro_rules! impl_fixed_len {
+    ($($ty:ty => $len:expr),* $(,)?) => {
+        $(
+            impl FixedLen for $ty {
+                const LEN: usize = $len;
+            }
+        )*
+    };
+}
+
+impl_fixed_len!(
+    bool => 1,
+    i8 => 1, i16 => 2, i32 => 4, i64 => 8, i128 => 16,
+    u8 => 1, u16 => 2, u32 => 4, u64 => 8, u128 => 16,
+    f32 => 4, f64 => 8,
+    // serialized by serde as `u64` and `i64`
+    usize => 8, isize => 8,
+    () => 0,
+);
+
+impl<T: FixedLen, const N: usize> FixedLen for [T; N] {
+    const LEN: usize = T::LEN * N;
+}
+
+impl<T> FixedLen for core::marker::PhantomData<T> {
+    const LEN: usize = 0;
+}
+
+/// A type whose memory is exactly its serialized bytes, so it can be viewed in place in a buffer,
+/// e.g. `u8`, the integers of `int` and the structures deriving `NetStruct` with `#[net_struct(zerocopy)]`.
+///
+/// # Safety
+/// The alignment of the type must be 1, it must have no padding, any bytes must be a valid value,
+/// and its memory must be the same as its serialized bytes.
+pub unsafe trait ZeroCopy: Sized + FixedLen {
+    /// views the leading bytes as `Self` without copying, fails with `SerdeErr::Eof` if there are not enough bytes.
+    /// The fields are not validated.
+    #[inline]
+    fn ref_from_bytes(bytes: &[u8]) -> Result<&Self, SerdeErr> {
+        match bytes.get(..core::mem::size_of::<Self>()) {
+            // SAFETY: the alignment is 1 and any bytes are a valid value
+            Some(b) => Ok(unsafe { &*(b.as_ptr() as *const Self) }),
+            None => Err(SerdeErr::Eof),
+        }
+    }
+
+    /// the same as `ref_from_bytes`, but the value can be modified in place
+    #[inline]
+    fn mut_from_bytes(bytes: &mut [u8]) -> Result<&mut Self, SerdeErr> {
+        match bytes.get_mut(..core::mem::size_of::<Self>()) {
+            // SAFETY: the alignment is 1 and any bytes are a valid value
+            Some(b) => Ok(unsafe { &mut *(b.as_mut_ptr() as *mut Self) }),
+            None => Err(SerdeErr::Eof),
+        }
+    }
+
+    /// the serialized bytes, without copying
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        // SAFETY: there is no padding, so all the bytes are initialised
+        unsafe {
+            core::slice::from_raw_parts(
+                self as *const Self as *const u8,
+                core::mem::size_of::<Self>(),
+            )
+        }
+    }
+}
+
+unsafe impl ZeroCopy for u8 {}
+unsafe impl ZeroCopy for i8 {}
+unsafe impl<T: ZeroCopy, const N: usize> ZeroCopy for [T; N] {}
+
+macro_rules! impl_len_field_for_primty {
+    ($primty:ty) => {
+        impl LenField for $primty {
+            #[inline]
+            fn to_len(&self) -> Result<usize, SerdeErr> {
+                usize::try_from(*self).map_err(|_| SerdeErr::OutOfRange)
+            }
+
+            #[inline]
+            fn from_len(len: usize) -> Result<Self, SerdeErr> {
+                <$primty>::try_from(len).map_err(|_| SerdeErr::OutOfRange)
+            }
+        }
+    };
+}
+
+impl_len_field_for_primty!(i8);
+impl_len_field_for_primty!(i16);
+impl_len_field_for_primty!(i32);
+impl_len_field_for_primty!(i64);
+impl_len_field_for_primty!(i128);
+impl_len_field_for_primty!(isize);
+impl_len_field_for_primty!(u8);
+impl_len_field_for_primty!(u16);
+impl_len_field_for_primty!(u32);
+impl_len_field_for_primty!(u64);
+impl_len_field_for_primty!(u128);
+impl_len_field_for_primty!(usize);
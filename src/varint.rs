@@ -0,0 +1,379 @@
+//! Variable-length integer encodings. All of them implement `LenField`,
+//! so they can also be used as the length field of `#[net_struct(vec_len(..))]`.
+//! They are decoded byte by byte from the front, so they cannot be deserialized in reverse.
+
+use crate::{
+    traits::{Deserialize, Deserializer, LenField, Placeholder, Serialize, Serializer},
+    SerdeErr,
+};
+
+/// QUIC variable-length integer (RFC 9000, section 16), the 2 most significant bits of the first byte
+/// give the length (1, 2, 4 or 8 bytes) of the big-endian integer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct QuicVarInt(pub u64);
+
+/// Little Endian Base 128, as used by Protobuf and WebAssembly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Leb128<T>(pub T);
+
+/// MQTT remaining length, the same as unsigned LEB128 but at most 4 bytes long
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct MqttVarLen(pub u32);
+
+/// ASN.1 DER length, either a single byte (short form) for lengths below 128,
+/// or `0x80 | N` followed by `N` big-endian bytes (long form)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct DerLength(pub u64);
+
+impl QuicVarInt {
+    pub const MAX: u64 = (1 << 62) - 1;
+}
+
+impl MqttVarLen {
+    pub const MAX: u32 = (1 << 28) - 1;
+}
+
+/// takes the next byte of a variable-length integer, which cannot be decoded in reverse
+#[inline]
+fn take_u8<D: Deserializer>(deserializer: D) -> Result<(D, u8), SerdeErr> {
+    if deserializer.is_reversed() {
+        return Err(SerdeErr::NotSupported);
+    }
+    let mut b = [0u8; 1];
+    let deserializer = deserializer.take(&mut b)?;
+    Ok((deserializer, b[0]))
+}
+
+/// encodes `v` as unsigned LEB128 into `buf`, and returns the number of bytes used
+fn encode_uleb128(mut v: u128, buf: &mut [u8]) -> Result<usize, SerdeErr> {
+    for (i, b) in buf.iter_mut().enumerate() {
+        *b = (v & 0x7f) as u8;
+        v >>= 7;
+        if v == 0 {
+            return Ok(i + 1);
+        }
+        *b |= 0x80;
+    }
+    Err(SerdeErr::OutOfRange)
+}
+
+/// decodes an unsigned LEB128 of at most `max_len` bytes
+fn decode_uleb128<D: Deserializer>(
+    mut deserializer: D,
+    max_len: usize,
+) -> Result<(D, u128), SerdeErr> {
+    let mut v = 0u128;
+    for i in 0..max_len {
+        let b;
+        (deserializer, b) = take_u8(deserializer)?;
+        v |= ((b & 0x7f) as u128) << (7 * i);
+        if b & 0x80 == 0 {
+            return Ok((deserializer, v));
+        }
+    }
+    Err(SerdeErr::ParseFailed)
+}
+
+impl Serialize for QuicVarInt {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.0.to_be_bytes();
+        let (prefix, len) = match self.0 {
+            0..=0x3f => (0x00, 1),
+            0x40..=0x3fff => (0x40, 2),
+            0x4000..=0x3fff_ffff => (0x80, 4),
+            0x4000_0000..=Self::MAX => (0xc0, 8),
+            _ => return Err(serde::ser::Error::custom(SerdeErr::OutOfRange)),
+        };
+        let mut buf = [0u8; 8];
+        buf[..len].copy_from_slice(&bytes[(8 - len)..]);
+        buf[0] |= prefix;
+        serializer.serialize_bytes(&buf[..len])
+    }
+}
+
+impl Deserialize for QuicVarInt {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let (mut deserializer, first) = take_u8(deserializer)?;
+        let len = 1usize << (first >> 6);
+        let mut v = (first & 0x3f) as u64;
+        for _ in 1..len {
+            let b;
+            (deserializer, b) = take_u8(deserializer)?;
+            v = (v << 8) | b as u64;
+        }
+        Ok(Self(v))
+    }
+}
+
+macro_rules! impl_leb128_unsigned {
+    ($primty:ty) => {
+        impl Serialize for Leb128<$primty> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut buf = [0u8; (<$primty>::BITS as usize).div_ceil(7)];
+                let len = encode_uleb128(self.0 as u128, &mut buf).map_err(serde::ser::Error::custom)?;
+                serializer.serialize_bytes(&buf[..len])
+            }
+        }
+
+        impl Deserialize for Leb128<$primty> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                let max_len = (<$primty>::BITS as usize).div_ceil(7);
+                let (_, v) = decode_uleb128(deserializer, max_len)?;
+                <$primty>::try_from(v)
+                    .map(Self)
+                    .map_err(|_| SerdeErr::ParseFailed)
+            }
+        }
+
+        impl LenField for Leb128<$primty> {
+            #[inline]
+            fn to_len(&self) -> Result<usize, SerdeErr> {
+                self.0.to_len()
+            }
+
+            #[inline]
+            fn from_len(len: usize) -> Result<Self, SerdeErr> {
+                <$primty>::from_len(len).map(Self)
+            }
+        }
+    };
+}
+
+macro_rules! impl_leb128_signed {
+    ($primty:ty) => {
+        impl Serialize for Leb128<$primty> {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let mut buf = [0u8; (<$primty>::BITS as usize).div_ceil(7)];
+                let mut v = self.0 as i128;
+                let mut len = 0;
+                loop {
+                    let b = (v & 0x7f) as u8;
+                    v >>= 7;
+                    let done = (v == 0 && b & 0x40 == 0) || (v == -1 && b & 0x40 != 0);
+                    buf[len] = if done { b } else { b | 0x80 };
+                    len += 1;
+                    if done {
+                        break;
+                    }
+                }
+                serializer.serialize_bytes(&buf[..len])
+            }
+        }
+
+        impl Deserialize for Leb128<$primty> {
+            fn deserialize<D>(mut deserializer: D) -> Result<Self, SerdeErr>
+            where
+                D: Deserializer,
+            {
+                let max_len = (<$primty>::BITS as usize).div_ceil(7);
+                let mut v = 0i128;
+                for i in 0..max_len {
+                    let b;
+                    (deserializer, b) = take_u8(deserializer)?;
+                    v |= ((b & 0x7f) as i128) << (7 * i);
+                    if b & 0x80 == 0 {
+                        if b & 0x40 != 0 {
+                            v |= -1i128 << (7 * (i + 1));
+                        }
+                        return <$primty>::try_from(v)
+                            .map(Self)
+                            .map_err(|_| SerdeErr::ParseFailed);
+                    }
+                }
+                Err(SerdeErr::ParseFailed)
+            }
+        }
+    };
+}
+
+impl_leb128_unsigned!(u8);
+impl_leb128_unsigned!(u16);
+impl_leb128_unsigned!(u32);
+impl_leb128_unsigned!(u64);
+impl_leb128_signed!(i8);
+impl_leb128_signed!(i16);
+impl_leb128_signed!(i32);
+impl_leb128_signed!(i64);
+
+impl Serialize for MqttVarLen {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut buf = [0u8; 4];
+        let len = encode_uleb128(self.0 as u128, &mut buf).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_bytes(&buf[..len])
+    }
+}
+
+impl Deserialize for MqttVarLen {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let (_, v) = decode_uleb128(deserializer, 4)?;
+        Ok(Self(v as u32))
+    }
+}
+
+impl Serialize for DerLength {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.0 < 0x80 {
+            return serializer.serialize_bytes(&[self.0 as u8]);
+        }
+        let bytes = self.0.to_be_bytes();
+        let len = bytes.len() - (self.0.leading_zeros() / 8) as usize;
+        let mut buf = [0u8; 9];
+        buf[0] = 0x80 | len as u8;
+        buf[1..=len].copy_from_slice(&bytes[(bytes.len() - len)..]);
+        serializer.serialize_bytes(&buf[..=len])
+    }
+}
+
+impl Deserialize for DerLength {
+    fn deserialize<D>(deserializer: D) -> Result<Self, SerdeErr>
+    where
+        D: Deserializer,
+    {
+        let (mut deserializer, first) = take_u8(deserializer)?;
+        if first & 0x80 == 0 {
+            return Ok(Self(first as u64));
+        }
+        let len = (first & 0x7f) as usize;
+        if len == 0 || len > core::mem::size_of::<u64>() {
+            return Err(SerdeErr::ParseFailed);
+        }
+        let mut v = 0u64;
+        for _ in 0..len {
+            let b;
+            (deserializer, b) = take_u8(deserializer)?;
+            v = (v << 8) | b as u64;
+        }
+        Ok(Self(v))
+    }
+}
+
+macro_rules! impl_len_field {
+    ($ty:ty, $primty:ty) => {
+        impl LenField for $ty {
+            #[inline]
+            fn to_len(&self) -> Result<usize, SerdeErr> {
+                self.0.to_len()
+            }
+
+            #[inline]
+            fn from_len(len: usize) -> Result<Self, SerdeErr> {
+                <$primty>::from_len(len).map(Self)
+            }
+        }
+
+        impl Placeholder for $ty {
+            #[inline]
+            fn placeholder() -> Self {
+                Self::default()
+            }
+        }
+    };
+}
+
+impl<T: Placeholder> Placeholder for Leb128<T> {
+    #[inline]
+    fn placeholder() -> Self {
+        Self(T::placeholder())
+    }
+}
+
+impl_len_field!(QuicVarInt, u64);
+impl_len_field!(MqttVarLen, u32);
+impl_len_field!(DerLength, u64);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{from_slice, to_vec};
+
+    fn round_trip<T>(value: T, correct_serialized: &[u8])
+    where
+        T: Serialize + Deserialize + PartialEq + core::fmt::Debug,
+    {
+        let serialized = to_vec::<16, T>(&value).unwrap();
+        assert_eq!(serialized.as_slice(), correct_serialized);
+        assert_eq!(from_slice::<T>(correct_serialized), Ok(value));
+    }
+
+    #[test]
+    fn quic() {
+        round_trip(QuicVarInt(37), &[0x25]);
+        round_trip(QuicVarInt(15293), &[0x7b, 0xbd]);
+        round_trip(QuicVarInt(494878333), &[0x9d, 0x7f, 0x3e, 0x7d]);
+        round_trip(
+            QuicVarInt(151288809941952652),
+            &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c],
+        );
+        assert_eq!(from_slice::<QuicVarInt>([0x40, 0x25]), Ok(QuicVarInt(37)));
+        assert!(to_vec::<16, _>(&QuicVarInt(QuicVarInt::MAX + 1)).is_err());
+    }
+
+    #[test]
+    fn leb128() {
+        round_trip(Leb128(624485u32), &[0xe5, 0x8e, 0x26]);
+        round_trip(Leb128(0u64), &[0x00]);
+        round_trip(Leb128(u64::MAX), &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        round_trip(Leb128(-123456i32), &[0xc0, 0xbb, 0x78]);
+        round_trip(Leb128(-1i8), &[0x7f]);
+        round_trip(Leb128(63i16), &[0x3f]);
+        round_trip(Leb128(64i16), &[0xc0, 0x00]);
+        assert_eq!(from_slice::<Leb128<u8>>([0x80, 0x02]), Err(SerdeErr::ParseFailed));
+        assert_eq!(from_slice::<Leb128<u8>>([0x80]), Err(SerdeErr::Eof));
+    }
+
+    #[test]
+    fn mqtt() {
+        round_trip(MqttVarLen(0), &[0x00]);
+        round_trip(MqttVarLen(127), &[0x7f]);
+        round_trip(MqttVarLen(128), &[0x80, 0x01]);
+        round_trip(MqttVarLen(MqttVarLen::MAX), &[0xff, 0xff, 0xff, 0x7f]);
+        assert!(to_vec::<16, _>(&MqttVarLen(MqttVarLen::MAX + 1)).is_err());
+        assert_eq!(
+            from_slice::<MqttVarLen>([0xff, 0xff, 0xff, 0xff, 0x01]),
+            Err(SerdeErr::ParseFailed)
+        );
+    }
+
+    #[test]
+    fn der() {
+        round_trip(DerLength(0x26), &[0x26]);
+        round_trip(DerLength(0x80), &[0x81, 0x80]);
+        round_trip(DerLength(0x0123), &[0x82, 0x01, 0x23]);
+        assert_eq!(from_slice::<DerLength>([0x80]), Err(SerdeErr::ParseFailed));
+    }
+
+    #[test]
+    fn reversed() {
+        let a = [0x25, 0x80, 0x01];
+        let mut nsd = crate::NetStructDeserializer::new(a.as_slice());
+        assert_eq!(QuicVarInt::deserialize((&mut nsd).reverse().unwrap()), Err(SerdeErr::NotSupported));
+        let mut nsd = crate::NetStructDeserializer::new(a.as_slice());
+        assert_eq!(MqttVarLen::deserialize((&mut nsd).reverse().unwrap()), Err(SerdeErr::NotSupported));
+        let mut nsd = crate::NetStructDeserializer::new(a.as_slice());
+        assert_eq!(Leb128::<i16>::deserialize((&mut nsd).reverse().unwrap()), Err(SerdeErr::NotSupported));
+    }
+}
@@ -0,0 +1,56 @@
+use net_struct_serde::traits::NetEnum;
+use net_struct_serde::*;
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[net_enum(repr([u8; 4]))]
+pub enum ChunkId {
+    #[net_enum(value = b"RIFF")]
+    Riff,
+    #[net_enum(value = b"LIST", alias = b"list")]
+    List,
+    #[net_enum(value = [0, 0, 0, 1])]
+    Raw,
+}
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[net_enum(repr([u8; 3]))]
+pub enum Vendor {
+    #[net_enum(value = [0x00, 0x00, 0x0c])]
+    Cisco,
+    #[net_enum(value = [0x00, 0x50, 0x56])]
+    VMware,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Chunk {
+    pub id: ChunkId,
+    pub size: u32,
+    pub vendor: Vendor,
+}
+
+#[test]
+fn fourcc() {
+    assert_eq!(ChunkId::try_from(*b"RIFF"), Ok(ChunkId::Riff));
+    assert_eq!(ChunkId::try_from(*b"list"), Ok(ChunkId::List));
+    assert_eq!(ChunkId::try_from(*b"WAVE"), Err(SerdeErr::ParseFailed));
+    assert_eq!(Into::<[u8; 4]>::into(ChunkId::List), *b"LIST");
+    assert_eq!(ChunkId::Raw.const_into(), [0, 0, 0, 1]);
+
+    const C: Chunk = Chunk {
+        id: ChunkId::Riff,
+        size: 4,
+        vendor: Vendor::VMware,
+    };
+    const CORRECT_SERIALIZED: [u8; 11] = [
+        b'R', b'I', b'F', b'F', 0x00, 0x00, 0x00, 0x04, 0x00, 0x50, 0x56,
+    ];
+    let serialized = to_vec::<16, Chunk>(&C).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Chunk>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(C, deserialized);
+    assert_eq!(from_slice::<ChunkId>(*b"list"), Ok(ChunkId::List));
+    assert_eq!(
+        from_slice::<Vendor>([0x00, 0x00, 0x0d]),
+        Err(SerdeErr::ParseFailed)
+    );
+}
@@ -0,0 +1,41 @@
+use net_struct_serde::traits::NetEnum;
+use net_struct_serde::*;
+
+const BASE: u16 = 0x100;
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[repr(u16)]
+pub enum Opcode {
+    Query,
+    IQuery,
+    Status,
+    Notify = 4,
+    Update,
+    Dso = BASE + 6,
+    Extended,
+    #[net_enum(range = 0x200..)]
+    Private(u16),
+}
+
+#[test]
+fn implicit() {
+    assert_eq!(Opcode::try_from(0), Ok(Opcode::Query));
+    assert_eq!(Opcode::try_from(2), Ok(Opcode::Status));
+    assert_eq!(Opcode::try_from(5), Ok(Opcode::Update));
+    assert_eq!(Opcode::try_from(0x107), Ok(Opcode::Extended));
+    assert_eq!(Opcode::try_from(3), Err(SerdeErr::ParseFailed));
+    assert_eq!(from_slice::<Opcode>([0x01, 0x06]), Ok(Opcode::Dso));
+    assert_eq!(
+        to_vec::<2, _>(&Opcode::Update).unwrap().as_slice(),
+        [0x00, 0x05]
+    );
+
+    assert_eq!(Opcode::VARIANTS.len(), 7);
+    assert_eq!(Opcode::VARIANTS[6], Opcode::Extended);
+    assert_eq!(Opcode::Notify.name(), "Notify");
+    assert_eq!(Opcode::Private(0x201).name(), "Private");
+    assert_eq!(Opcode::from_name("IQuery"), Some(Opcode::IQuery));
+    assert_eq!(Opcode::from_name("Private"), None);
+    assert_eq!(format!("{}", Opcode::Status), "Status");
+    assert_eq!(format!("{}", Opcode::Private(0x201)), "Private(513)");
+}
@@ -1 +1,4 @@
+mod fourcc;
+mod implicit;
+mod range;
 mod simple;
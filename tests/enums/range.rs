@@ -0,0 +1,61 @@
+use net_struct_serde::traits::NetEnum;
+use net_struct_serde::*;
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[net_enum(repr(u8))]
+#[repr(u8)]
+enum IpProtocol {
+    Icmp = 1,
+    #[net_enum(alias = 0x80, 0x81)]
+    Tcp = 6,
+    Udp = 17,
+    #[net_enum(range = 0x90..=0xfe)]
+    Experimental(u8),
+    #[net_enum(range = 0xff..)]
+    Reserved(u8),
+}
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[net_enum(repr(u16))]
+enum Port {
+    #[net_enum(range = ..1024)]
+    WellKnown(u16),
+    #[net_enum(range = 1024..)]
+    Registered(u16),
+}
+
+#[test]
+fn range() {
+    assert_eq!(IpProtocol::try_from(6), Ok(IpProtocol::Tcp));
+    assert_eq!(IpProtocol::try_from(0x81), Ok(IpProtocol::Tcp));
+    assert_eq!(
+        IpProtocol::try_from(0x90),
+        Ok(IpProtocol::Experimental(0x90))
+    );
+    assert_eq!(IpProtocol::try_from(0xff), Ok(IpProtocol::Reserved(0xff)));
+    assert_eq!(IpProtocol::try_from(0x82), Err(SerdeErr::ParseFailed));
+    assert_eq!(Into::<u8>::into(IpProtocol::Experimental(0xa0)), 0xa0);
+
+    // the canonical value is serialized for an alias
+    let deserialized = from_slice::<IpProtocol>([0x80]).unwrap();
+    assert_eq!(deserialized, IpProtocol::Tcp);
+    assert_eq!(to_vec::<1, _>(&deserialized).unwrap().as_slice(), [6]);
+    assert_eq!(
+        to_vec::<1, _>(&IpProtocol::Experimental(0xfe))
+            .unwrap()
+            .as_slice(),
+        [0xfe]
+    );
+    assert!(to_vec::<1, _>(&IpProtocol::Experimental(0x10)).is_err());
+    assert_eq!(
+        <IpProtocol as traits::Placeholder>::placeholder(),
+        IpProtocol::Icmp
+    );
+
+    assert_eq!(from_slice::<Port>([0x00, 0x50]), Ok(Port::WellKnown(80)));
+    assert_eq!(from_slice::<Port>([0x04, 0x00]), Ok(Port::Registered(1024)));
+    assert_eq!(
+        <Port as traits::Placeholder>::placeholder(),
+        Port::WellKnown(0)
+    );
+}
@@ -0,0 +1,71 @@
+use net_struct_serde::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, NetFlags)]
+#[net_flags(FIN = 0x01, SYN = 0x02, RST = 0x04, PSH = 0x08, ACK = 0x10, URG = 0x20)]
+pub struct TcpFlags(u8);
+
+#[derive(Clone, Copy, PartialEq, Eq, NetFlags)]
+#[net_flags(QR = 0x8000, AA = 0x0400, TC = 0x0200, RD = 0x0100, RA = 0x0080)]
+#[net_flags(strict)]
+pub struct DnsFlags(u16);
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Segment {
+    pub offset: u8,
+    pub flags: TcpFlags,
+    pub window: u16,
+}
+
+#[test]
+fn tcp() {
+    let mut flags = TcpFlags::SYN | TcpFlags::ACK;
+    assert_eq!(flags.bits(), 0x12);
+    assert!(flags.contains(TcpFlags::SYN));
+    assert!(!flags.contains(TcpFlags::SYN | TcpFlags::FIN));
+    assert!(flags.intersects(TcpFlags::SYN | TcpFlags::FIN));
+    flags.remove(TcpFlags::SYN);
+    flags.insert(TcpFlags::PSH);
+    flags.set(TcpFlags::FIN, true);
+    assert_eq!(flags, TcpFlags::ACK | TcpFlags::PSH | TcpFlags::FIN);
+    assert_eq!(format!("{:?}", flags), "TcpFlags(FIN | PSH | ACK)");
+    assert_eq!(format!("{:?}", TcpFlags::empty()), "TcpFlags(0x0)");
+    assert_eq!(!TcpFlags::ALL, TcpFlags::empty());
+    assert_eq!(
+        TcpFlags::ALL - TcpFlags::URG,
+        TcpFlags::from_bits_truncate(0xdf)
+    );
+    assert_eq!(
+        flags.iter_names().map(|(n, _)| n).collect::<Vec<_>>(),
+        ["FIN", "PSH", "ACK"]
+    );
+
+    const S: Segment = Segment {
+        offset: 5,
+        flags: TcpFlags::from_bits_retain(0xc2),
+        window: 0x0102,
+    };
+    const CORRECT_SERIALIZED: [u8; 4] = [5, 0xc2, 0x01, 0x02];
+    let serialized = to_vec::<8, Segment>(&S).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Segment>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+    assert_eq!(deserialized.flags.unknown_bits(), 0xc0);
+    assert_eq!(format!("{:?}", deserialized.flags), "TcpFlags(SYN | 0xc0)");
+}
+
+#[test]
+fn strict() {
+    assert_eq!(
+        from_slice::<DnsFlags>([0x81, 0x80]),
+        Ok(DnsFlags::QR | DnsFlags::RD | DnsFlags::RA)
+    );
+    assert_eq!(
+        from_slice::<DnsFlags>([0x81, 0x81]).err(),
+        Some(SerdeErr::InvalidValue("DnsFlags"))
+    );
+    assert_eq!(DnsFlags::from_bits(0x0001).ok(), None);
+    assert_eq!(
+        <DnsFlags as traits::NetFlags>::from_bits_retain(0x0001),
+        DnsFlags::from_bits_retain(0x0001)
+    );
+}
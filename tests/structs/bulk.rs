@@ -0,0 +1,35 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Samples {
+    pub count: u8,
+    #[net_struct(vec_len(count))]
+    pub values: [i16; 4],
+    pub gains: [f32; 2],
+    pub tail: [u8; 3],
+}
+
+#[test]
+fn bulk() {
+    const S: Samples = Samples {
+        count: 3,
+        values: [-1, 0x0102, 0x0304, 0],
+        gains: [1.0, -2.5],
+        tail: [7, 8, 9],
+    };
+    const CORRECT_SERIALIZED: [u8; 18] = [
+        3, 0xff, 0xff, 0x01, 0x02, 0x03, 0x04, 0x3f, 0x80, 0x00, 0x00, 0xc0, 0x20, 0x00, 0x00, 7,
+        8, 9,
+    ];
+    let serialized = to_vec::<32, Samples>(&S).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    assert_eq!(encoded_len(&S), Ok(CORRECT_SERIALIZED.len()));
+    let deserialized = from_slice::<Samples>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+    assert_eq!(deserialized.gains, [1.0, -2.5]);
+    assert_eq!(
+        from_slice::<Samples>(&CORRECT_SERIALIZED[..17]),
+        Err(SerdeErr::Eof)
+    );
+    assert_eq!(to_vec::<17, Samples>(&S), Err(SerdeErr::NotEnoughSpace));
+}
@@ -0,0 +1,67 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Tlv {
+    pub kind: u8,
+    pub len: u8,
+    #[net_struct(vec_len(len))]
+    pub value: [u8; 4],
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Options {
+    pub total: u8,
+    #[net_struct(vec_len(total, bytes))]
+    pub options: [Tlv; 4],
+    pub tail: u8,
+}
+
+const TLV_A: Tlv = Tlv {
+    kind: 1,
+    len: 3,
+    value: [1, 2, 3, 0],
+};
+const TLV_B: Tlv = Tlv {
+    kind: 2,
+    len: 0,
+    value: [0; 4],
+};
+
+#[test]
+fn byte_len() {
+    const S: Options = Options {
+        total: 7,
+        options: [TLV_A, TLV_B, TLV_B, TLV_B],
+        tail: 0xaa,
+    };
+    const CORRECT_SERIALIZED: [u8; 9] = [7, 1, 3, 1, 2, 3, 2, 0, 0xaa];
+    let serialized = to_vec::<32, Options>(&S).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Options>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+    assert_eq!(deserialized.options[0], TLV_A);
+    assert_eq!(deserialized.options[1], TLV_B);
+    assert_eq!(deserialized.tail, 0xaa);
+
+    // the elements beyond the byte length are not compared
+    let mut other = S;
+    other.options[2] = TLV_A;
+    assert_eq!(S, other);
+
+    // the second element straddles the end of the vector
+    let mut straddling = CORRECT_SERIALIZED;
+    straddling[0] = 6;
+    assert_eq!(
+        from_slice::<Options>(straddling).err(),
+        Some(SerdeErr::ParseFailed)
+    );
+    // more elements than the capacity of the array
+    let overflowing = [10, 2, 0, 2, 0, 2, 0, 2, 0, 2, 0, 0xaa];
+    assert_eq!(
+        from_slice::<Options>(overflowing).err(),
+        Some(SerdeErr::OutOfRange)
+    );
+    let mut inconsistent = S;
+    inconsistent.total = 6;
+    assert!(to_vec::<32, Options>(&inconsistent).is_err());
+}
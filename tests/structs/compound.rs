@@ -0,0 +1,38 @@
+use core::marker::PhantomData;
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Frame {
+    pub point: (u16, u16),
+    pub valid: bool,
+    pub grade: char,
+    pub unit: (),
+    pub marker: PhantomData<u32>,
+    pub len: u8,
+    #[net_struct(vec_len(len))]
+    pub blocks: [[u8; 2]; 3],
+    pub tail: (u8, char, [i8; 2]),
+}
+
+#[test]
+fn compound() {
+    const F: Frame = Frame {
+        point: (0x0102, 0x0304),
+        valid: true,
+        grade: 'é',
+        unit: (),
+        marker: PhantomData,
+        len: 2,
+        blocks: [[5, 6], [7, 8], [0, 0]],
+        tail: (9, 'z', [-1, 1]),
+    };
+    const CORRECT_SERIALIZED: [u8; 16] = [
+        0x01, 0x02, 0x03, 0x04, 0x01, 0xc3, 0xa9, 0x02, 5, 6, 7, 8, 9, b'z', 0xff, 0x01,
+    ];
+    let serialized = to_vec::<32, Frame>(&F).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Frame>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(F, deserialized);
+    assert_eq!(deserialized.grade, 'é');
+    assert_eq!(deserialized.tail, (9, 'z', [-1, 1]));
+}
@@ -0,0 +1,70 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Gre {
+    pub flags: u8,
+    pub protocol: u16,
+    #[net_struct(if = flags & 0x20 != 0, check_if)]
+    pub key: Option<u32>,
+    #[net_struct(if = "flags & 0x10 != 0")]
+    pub sequence: Option<u32>,
+    pub tail: u8,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Trailer {
+    pub data: u8,
+    #[net_struct(if = has_crc)]
+    pub crc: Option<u16>,
+    pub has_crc: bool,
+}
+
+#[test]
+fn conditional() {
+    const G: Gre = Gre {
+        flags: 0x20,
+        protocol: 0x0800,
+        key: Some(0x01020304),
+        sequence: None,
+        tail: 9,
+    };
+    const CORRECT_SERIALIZED: [u8; 8] = [0x20, 0x08, 0x00, 1, 2, 3, 4, 9];
+    let serialized = to_vec::<32, Gre>(&G).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Gre>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(G, deserialized);
+    assert_eq!(deserialized.sequence, None);
+    assert_eq!(deserialized.tail, 9);
+
+    let both = from_slice::<Gre>([0x30, 0x08, 0x00, 1, 2, 3, 4, 0, 0, 0, 5, 9]).unwrap();
+    assert_eq!(both.key, Some(0x01020304));
+    assert_eq!(both.sequence, Some(5));
+    let neither = from_slice::<Gre>([0x00, 0x08, 0x00, 9]).unwrap();
+    assert_eq!((neither.key, neither.sequence, neither.tail), (None, None, 9));
+
+    // the key is checked against the flags, the sequence number is not
+    let mut mismatched = G;
+    mismatched.key = None;
+    assert!(to_vec::<32, Gre>(&mismatched).is_err());
+    mismatched = G;
+    mismatched.sequence = Some(5);
+    assert_eq!(to_vec::<32, Gre>(&mismatched).unwrap().len(), 12);
+
+    // the flag is after the conditional field
+    const T: Trailer = Trailer {
+        data: 1,
+        crc: Some(0xabcd),
+        has_crc: true,
+    };
+    let serialized = to_vec::<32, Trailer>(&T).unwrap();
+    assert_eq!(serialized.as_slice(), [1, 0xab, 0xcd, 1]);
+    assert_eq!(from_slice::<Trailer>(serialized).unwrap(), T);
+    assert_eq!(
+        from_slice::<Trailer>([1, 0]).unwrap(),
+        Trailer {
+            data: 1,
+            crc: None,
+            has_crc: false
+        }
+    );
+}
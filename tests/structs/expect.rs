@@ -0,0 +1,60 @@
+use net_struct_serde::*;
+
+fn is_even(v: &u8) -> bool {
+    v.is_multiple_of(2)
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeHeader {
+    #[net_struct(const = 0xa1b2c3d4)]
+    pub magic: u32,
+    #[net_struct(expect = 4)]
+    pub version: u8,
+    #[net_struct(range = 1..=5)]
+    pub level: u8,
+    #[net_struct(validate = is_even)]
+    pub even: u8,
+    #[net_struct(const = [0, 0])]
+    pub reserved: [u8; 2],
+}
+
+#[test]
+fn expect() {
+    const S: SomeHeader = SomeHeader {
+        magic: 0,
+        version: 4,
+        level: 3,
+        even: 8,
+        reserved: [0xff, 0xff],
+    };
+    const CORRECT_SERIALIZED: [u8; 9] = [0xa1, 0xb2, 0xc3, 0xd4, 4, 3, 8, 0, 0];
+    let mut serialized = [0u8; CORRECT_SERIALIZED.len()];
+    let mut serializer = net_struct_serde::NetStructSerializer::new(&mut serialized);
+    S.serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.finalize(), CORRECT_SERIALIZED.len());
+    assert_eq!(serialized, CORRECT_SERIALIZED);
+    let deserialized = from_slice::<SomeHeader>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+    assert_eq!(deserialized.magic, 0xa1b2c3d4);
+    assert_eq!(deserialized.reserved, [0, 0]);
+    assert_eq!(
+        from_slice::<SomeHeader>([0xa1, 0xb2, 0xc3, 0xd5, 4, 3, 8, 0, 0]),
+        Err(SerdeErr::InvalidValue("magic"))
+    );
+    assert_eq!(
+        from_slice::<SomeHeader>([0xa1, 0xb2, 0xc3, 0xd4, 6, 3, 8, 0, 0]),
+        Err(SerdeErr::InvalidValue("version"))
+    );
+    assert_eq!(
+        from_slice::<SomeHeader>([0xa1, 0xb2, 0xc3, 0xd4, 4, 0, 8, 0, 0]),
+        Err(SerdeErr::InvalidValue("level"))
+    );
+    assert_eq!(
+        from_slice::<SomeHeader>([0xa1, 0xb2, 0xc3, 0xd4, 4, 3, 7, 0, 0]),
+        Err(SerdeErr::InvalidValue("even"))
+    );
+    assert_eq!(
+        from_slice::<SomeHeader>([0xa1, 0xb2, 0xc3, 0xd4, 4, 3, 8, 0, 1]),
+        Err(SerdeErr::InvalidValue("reserved"))
+    );
+}
@@ -0,0 +1,28 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeStruct {
+    pub field1: u8,
+    #[net_struct(field_vec_len(vec1, bytes))]
+    pub vec1_bytes: u8,
+    pub vec1: [u16; 8],
+    pub vec2: [u8; 4],
+    #[net_struct(field_vec_len(vec2))]
+    pub vec2_len: u8,
+}
+
+#[test]
+fn field_vec_len() {
+    const S: SomeStruct = SomeStruct {
+        field1: 99,
+        vec1_bytes: 4,
+        vec1: [4, 5, 0, 0, 0, 0, 0, 0],
+        vec2: [7, 8, 9, 0],
+        vec2_len: 3,
+    };
+    const CORRECT_SERIALIZED: [u8; 10] = [99, 4, 0, 4, 0, 5, 7, 8, 9, 3];
+    let serialized = to_vec::<32, SomeStruct>(&S).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<SomeStruct>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+}
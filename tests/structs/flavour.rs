@@ -0,0 +1,61 @@
+use net_struct_serde::{
+    flavour::{Bcd, FixedPoint, OffsetBinary},
+    *,
+};
+
+mod centi {
+    use net_struct_serde::SerdeErr;
+
+    pub fn encode(val: &f32) -> Result<u16, SerdeErr> {
+        Ok((val * 100.0).round() as u16)
+    }
+
+    pub fn decode(wire: u16) -> Result<f32, SerdeErr> {
+        Ok(wire as f32 / 100.0)
+    }
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SensorReading {
+    #[net_struct(flavour = Bcd)]
+    pub year: u16,
+    #[net_struct(flavour = FixedPoint<i16, 8>)]
+    pub temperature: f32,
+    #[net_struct(with = centi)]
+    pub voltage: f32,
+    #[net_struct(flavour = OffsetBinary)]
+    pub offset: i8,
+}
+
+#[test]
+fn flavour() {
+    const S: SensorReading = SensorReading {
+        year: 2024,
+        temperature: -1.5,
+        voltage: 3.3,
+        offset: -2,
+    };
+    const CORRECT_SERIALIZED: [u8; 7] = [0x20, 0x24, 0xfe, 0x80, 0x01, 0x4a, 0x7e];
+    let mut serialized = [0u8; CORRECT_SERIALIZED.len()];
+    let mut serializer = net_struct_serde::NetStructSerializer::new(&mut serialized);
+    S.serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.finalize(), CORRECT_SERIALIZED.len());
+    assert_eq!(serialized, CORRECT_SERIALIZED);
+    let mut deserializer = net_struct_serde::NetStructDeserializer::new(&CORRECT_SERIALIZED);
+    let deserialized = SensorReading::deserialize(&mut deserializer).unwrap();
+    assert_eq!(S, deserialized);
+    assert_eq!(deserializer.finalize(), CORRECT_SERIALIZED.len());
+    assert_eq!(
+        from_slice::<SensorReading>([0x20, 0x2a, 0xfe, 0x80, 0x01, 0x4a, 0x7e]),
+        Err(SerdeErr::ParseFailed)
+    );
+    assert!(to_vec::<8, _>(&SensorReading { year: 10000, ..S }).is_err());
+    type Q1_63 = FixedPoint<i64, 63>;
+    assert_eq!(<Q1_63 as traits::Flavour<f64>>::encode(&-0.5), Ok(i64::MIN / 2));
+    assert_eq!(<Q1_63 as traits::Flavour<f64>>::decode(i64::MIN), Ok(-1.0));
+    assert_eq!(<Q1_63 as traits::Flavour<f64>>::encode(&-1.0), Ok(i64::MIN));
+    assert_eq!(<Q1_63 as traits::Flavour<f64>>::encode(&1.0), Err(SerdeErr::OutOfRange));
+    type U64 = FixedPoint<u64, 0>;
+    assert_eq!(<U64 as traits::Flavour<f32>>::encode(&(u64::MAX as f32)), Err(SerdeErr::OutOfRange));
+    assert_eq!(<U64 as traits::Flavour<f32>>::encode(&-1.0), Err(SerdeErr::OutOfRange));
+}
@@ -0,0 +1,62 @@
+use net_struct_serde::{flavour::StrictBool, traits::NetEnum, *};
+
+#[derive(Debug, NetEnum, PartialEq, Eq, Clone, Copy)]
+#[net_enum(repr(u8))]
+pub enum Kind {
+    Data = 1,
+    Ack = 2,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Item {
+    pub kind: Kind,
+    pub flag: bool,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(struct_len(total_len, bytes))]
+pub struct Message {
+    pub total_len: u16,
+    #[net_struct(flavour = StrictBool)]
+    pub urgent: bool,
+    #[net_struct(reserved(1))]
+    pub _reserved: u8,
+    pub kinds: [Kind; 2],
+    pub num_items: u8,
+    #[net_struct(vec_len(num_items))]
+    pub items: [Item; 4],
+    #[net_struct(phantom)]
+    pub cached: bool,
+    #[net_struct(phantom, default = Kind::Ack)]
+    pub last_kind: Kind,
+}
+
+#[test]
+fn init() {
+    let item = Item {
+        kind: Kind::Data,
+        flag: true,
+    };
+    let s = Message {
+        total_len: 11,
+        urgent: true,
+        _reserved: 0,
+        kinds: [Kind::Ack, Kind::Data],
+        num_items: 2,
+        items: [item, item, item, item],
+        cached: true,
+        last_kind: Kind::Data,
+    };
+    const CORRECT_SERIALIZED: [u8; 12] = [0, 11, 1, 0, 2, 1, 2, 1, 1, 1, 1, 0xee];
+    let serialized = to_vec::<16, Message>(&s).unwrap();
+    assert_eq!(serialized.as_slice(), &CORRECT_SERIALIZED[..11]);
+    let deserialized = from_slice::<Message>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(s, deserialized);
+    assert!(!deserialized.cached);
+    assert_eq!(deserialized.last_kind, Kind::Ack);
+    assert_eq!(deserialized.items[2].kind, Kind::Data);
+    assert!(!deserialized.items[2].flag);
+    let mut short = CORRECT_SERIALIZED;
+    short[1] = 3;
+    assert_eq!(from_slice::<Message>(short).err(), Some(SerdeErr::Eof));
+}
@@ -0,0 +1,32 @@
+use net_struct_serde::{int::*, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Handshake {
+    pub msg_type: u8,
+    pub msg_len: U24,
+    pub label: U24Le,
+    pub counter: I48,
+    #[net_struct(vec_len(msg_len, bytes))]
+    pub body: [u16; 4],
+}
+
+#[test]
+fn int() {
+    let s = Handshake {
+        msg_type: 1,
+        msg_len: U24::try_from(4u32).unwrap(),
+        label: U24Le::try_from(0x0a0b0cu32).unwrap(),
+        counter: I48::try_from(-2i64).unwrap(),
+        body: [0xaabb, 0xccdd, 0, 0],
+    };
+    const CORRECT_SERIALIZED: [u8; 17] = [
+        0x01, 0x00, 0x00, 0x04, 0x0c, 0x0b, 0x0a, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe, 0xaa, 0xbb,
+        0xcc, 0xdd,
+    ];
+    let serialized = to_vec::<32, Handshake>(&s).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Handshake>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(s, deserialized);
+    assert_eq!(u32::from(deserialized.msg_len), 4);
+    assert_eq!(i64::from(deserialized.counter), -2);
+}
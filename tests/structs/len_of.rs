@@ -0,0 +1,92 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Datagram {
+    pub src_port: u16,
+    pub dst_port: u16,
+    #[net_struct(len_of(self))]
+    pub length: u16,
+    pub checksum: u16,
+    #[net_struct(vec_len(length))]
+    pub payload: [u8; 16],
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Attribute {
+    pub kind: u8,
+    #[net_struct(len_of(flags, value, words(2)))]
+    pub len: u8,
+    pub flags: u16,
+    #[net_struct(vec_len(len))]
+    pub value: [u16; 4],
+    pub trailer: u8,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct PaddedOption {
+    pub kind: u8,
+    #[net_struct(len_of(kind, len, n, data, words(4)))]
+    pub len: u8,
+    pub n: u8,
+    #[net_struct(vec_len(n))]
+    pub data: [u8; 8],
+    pub next: u8,
+}
+
+#[test]
+fn len_of_self() {
+    const D: Datagram = Datagram {
+        src_port: 53,
+        dst_port: 1024,
+        length: 11,
+        checksum: 0xabcd,
+        payload: [1, 2, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+    };
+    const CORRECT_SERIALIZED: [u8; 12] = [0, 53, 4, 0, 0, 11, 0xab, 0xcd, 1, 2, 3, 0xee];
+    let serialized = to_vec::<32, Datagram>(&D).unwrap();
+    assert_eq!(serialized.as_slice(), &CORRECT_SERIALIZED[..11]);
+    let deserialized = from_slice::<Datagram>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(D, deserialized);
+    assert_eq!(deserialized.payload[3], 0);
+    let mut short = CORRECT_SERIALIZED;
+    short[5] = 7;
+    assert_eq!(
+        from_slice::<Datagram>(short).err(),
+        Some(SerdeErr::InvalidValue("length"))
+    );
+    let mut long = CORRECT_SERIALIZED;
+    long[5] = 13;
+    assert_eq!(from_slice::<Datagram>(long).err(), Some(SerdeErr::Eof));
+}
+
+#[test]
+fn len_of_fields() {
+    const A: Attribute = Attribute {
+        kind: 7,
+        len: 2,
+        flags: 0x8000,
+        value: [0x1234, 0, 0, 0],
+        trailer: 9,
+    };
+    const CORRECT_SERIALIZED: [u8; 7] = [7, 2, 0x80, 0, 0x12, 0x34, 9];
+    let serialized = to_vec::<32, Attribute>(&A).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Attribute>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(A, deserialized);
+    assert_eq!(deserialized.trailer, 9);
+
+    // the group is 4 bytes long, the last byte is padding
+    const O: PaddedOption = PaddedOption {
+        kind: 1,
+        len: 1,
+        n: 0,
+        data: [0; 8],
+        next: 5,
+    };
+    const CORRECT_SERIALIZED_OPTION: [u8; 5] = [1, 1, 0, 0, 5];
+    let serialized = to_vec::<32, PaddedOption>(&O).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED_OPTION);
+    let deserialized = from_slice::<PaddedOption>([1, 1, 0, 0xff, 5]).unwrap();
+    assert_eq!(O, deserialized);
+    assert_eq!(deserialized.next, 5);
+}
@@ -0,0 +1,58 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(struct_len(total, words(2)))]
+pub struct SomeHeader {
+    pub total: u8,
+    pub ihl: u8,
+    pub len_minus_one: u8,
+    pub num_pairs: u8,
+    #[net_struct(vec_len(ihl, words(4), offset = -4))]
+    pub options: [u8; 8],
+    #[net_struct(vec_len(len_minus_one, offset = 1))]
+    pub data: [u16; 4],
+    #[net_struct(vec_len(num_pairs, expr = num_pairs * 2))]
+    pub pairs: [u8; 6],
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeTail {
+    pub x: u8,
+    #[net_struct(vec_len(len_plus_one, bytes, offset = -1))]
+    pub vec: [u16; 4],
+    #[net_struct(phantom)]
+    pub len_plus_one: usize,
+}
+
+#[test]
+fn len_unit() {
+    const S: SomeHeader = SomeHeader {
+        total: 7,
+        ihl: 2,
+        len_minus_one: 1,
+        num_pairs: 1,
+        options: [1, 2, 3, 4, 0, 0, 0, 0],
+        data: [10, 11, 0, 0],
+        pairs: [5, 6, 0, 0, 0, 0],
+    };
+    const CORRECT_SERIALIZED: [u8; 15] = [7, 2, 1, 1, 1, 2, 3, 4, 0, 10, 0, 11, 5, 6, 0xee];
+    let serialized = to_vec::<32, SomeHeader>(&S).unwrap();
+    assert_eq!(serialized.as_slice(), &CORRECT_SERIALIZED[..14]);
+    let deserialized = from_slice::<SomeHeader>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(S, deserialized);
+    let mut truncated = CORRECT_SERIALIZED;
+    truncated[0] = 6;
+    assert_eq!(from_slice::<SomeHeader>(truncated).err(), Some(SerdeErr::Eof));
+
+    const T: SomeTail = SomeTail {
+        x: 3,
+        vec: [1, 2, 0, 0],
+        len_plus_one: 5,
+    };
+    const CORRECT_SERIALIZED_TAIL: [u8; 5] = [3, 0, 1, 0, 2];
+    let serialized = to_vec::<32, SomeTail>(&T).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED_TAIL);
+    let deserialized = from_slice::<SomeTail>(CORRECT_SERIALIZED_TAIL).unwrap();
+    assert_eq!(T, deserialized);
+    assert_eq!(deserialized.len_plus_one, 5);
+}
@@ -1,6 +1,27 @@
+mod bulk;
+mod byte_len;
+mod compound;
+mod conditional;
+mod expect;
+mod field_vec_len;
+mod flavour;
+mod init;
+mod int;
+mod len_of;
+mod len_unit;
+mod net;
+mod option;
+mod padding;
 mod phantom;
 mod phantom2;
 mod reverse;
 mod reverse2;
 mod sample;
 mod simple;
+mod string;
+mod until;
+mod varint;
+mod version;
+mod view;
+mod wide;
+mod zerocopy;
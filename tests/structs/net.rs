@@ -0,0 +1,49 @@
+use core::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4};
+use net_struct_serde::{flavour::StrictBool, net::MacAddr, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Neighbour {
+    pub mac: MacAddr,
+    pub ipv4: Ipv4Addr,
+    pub ipv6: Ipv6Addr,
+    pub peer: SocketAddrV4,
+    #[net_struct(flavour = StrictBool)]
+    pub is_router: bool,
+    pub is_static: bool,
+    pub num_prefixes: u8,
+    #[net_struct(vec_len(num_prefixes))]
+    pub prefixes: [Ipv4Addr; 4],
+}
+
+#[test]
+fn net() {
+    let s = Neighbour {
+        mac: MacAddr::new(0x00, 0x1b, 0x21, 0x3a, 0x4c, 0x5d),
+        ipv4: Ipv4Addr::new(10, 0, 0, 1),
+        ipv6: Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 1),
+        peer: SocketAddrV4::new(Ipv4Addr::new(10, 0, 0, 2), 179),
+        is_router: true,
+        is_static: false,
+        num_prefixes: 2,
+        prefixes: [
+            Ipv4Addr::new(192, 168, 0, 0),
+            Ipv4Addr::new(172, 16, 0, 0),
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::UNSPECIFIED,
+        ],
+    };
+    const CORRECT_SERIALIZED: [u8; 43] = [
+        0x00, 0x1b, 0x21, 0x3a, 0x4c, 0x5d, 10, 0, 0, 1, 0xfe, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        0, 0, 0, 1, 10, 0, 0, 2, 0, 179, 1, 0, 2, 192, 168, 0, 0, 172, 16, 0, 0,
+    ];
+    let serialized = to_vec::<64, Neighbour>(&s).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<Neighbour>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(s, deserialized);
+    let mut invalid = CORRECT_SERIALIZED;
+    invalid[32] = 2;
+    assert_eq!(
+        from_slice::<Neighbour>(invalid).err(),
+        Some(SerdeErr::ParseFailed)
+    );
+}
@@ -0,0 +1,65 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Optionals {
+    #[net_struct(option(presence))]
+    pub flagged: Option<u16>,
+    #[net_struct(option(sentinel = 0xffff))]
+    pub port: Option<u16>,
+    #[net_struct(option(len_prefix = u8))]
+    pub addr: Option<u32>,
+    pub x: u8,
+    #[net_struct(option(trailing))]
+    pub extra: Option<u16>,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Empty {
+    #[net_struct(option(len_prefix = u8))]
+    pub marker: Option<core::marker::PhantomData<u8>>,
+}
+
+#[test]
+fn option() {
+    const ALL: Optionals = Optionals {
+        flagged: Some(0x0102),
+        port: Some(80),
+        addr: Some(0x0a000001),
+        x: 7,
+        extra: Some(0x0304),
+    };
+    const ALL_SERIALIZED: [u8; 14] = [1, 1, 2, 0, 80, 4, 10, 0, 0, 1, 7, 3, 4, 0xee];
+    let serialized = to_vec::<32, Optionals>(&ALL).unwrap();
+    assert_eq!(serialized.as_slice(), &ALL_SERIALIZED[..13]);
+    assert_eq!(from_slice::<Optionals>(&ALL_SERIALIZED[..13]), Ok(ALL));
+
+    const NONE: Optionals = Optionals {
+        flagged: None,
+        port: None,
+        addr: None,
+        x: 7,
+        extra: None,
+    };
+    const NONE_SERIALIZED: [u8; 5] = [0, 0xff, 0xff, 0, 7];
+    let serialized = to_vec::<32, Optionals>(&NONE).unwrap();
+    assert_eq!(serialized.as_slice(), NONE_SERIALIZED);
+    assert_eq!(from_slice::<Optionals>(NONE_SERIALIZED), Ok(NONE));
+
+    assert_eq!(
+        from_slice::<Optionals>([2, 0xff, 0xff, 0, 7]),
+        Err(SerdeErr::InvalidValue("flagged"))
+    );
+    assert_eq!(
+        from_slice::<Optionals>([0, 0xff, 0xff, 2, 0, 0, 0, 1, 7]),
+        Err(SerdeErr::InvalidValue("addr"))
+    );
+    let mut sentinel = NONE;
+    sentinel.port = Some(0xffff);
+    assert!(to_vec::<32, Optionals>(&sentinel).is_err());
+
+    // a value of no bytes cannot be told from `None`
+    let empty = Empty { marker: Some(core::marker::PhantomData) };
+    assert!(to_vec::<32, Empty>(&empty).is_err());
+    let serialized = to_vec::<32, Empty>(&Empty { marker: None }).unwrap();
+    assert_eq!(serialized.as_slice(), [0]);
+}
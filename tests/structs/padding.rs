@@ -0,0 +1,74 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(pad_to(4))]
+pub struct SomeAttr {
+    pub kind: u8,
+    #[net_struct(reserved(1))]
+    pub _reserved: u8,
+    pub name_len: u8,
+    #[net_struct(vec_len(name_len), pad_to(4))]
+    pub name: [u8; 8],
+    pub flag: u8,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeMessage {
+    pub version: u8,
+    pub attrs_len: u8,
+    #[net_struct(vec_len(attrs_len))]
+    pub attrs: [SomeAttr; 4],
+}
+
+#[test]
+fn padding() {
+    const ATTR: SomeAttr = SomeAttr {
+        kind: 1,
+        _reserved: 0xff,
+        name_len: 3,
+        name: [b'a', b'b', b'c', 0, 0, 0, 0, 0],
+        flag: 7,
+    };
+    const CORRECT_SERIALIZED: [u8; 12] = [1, 0, 3, b'a', b'b', b'c', 0, 0, 7, 0, 0, 0];
+    let mut serialized = [0xffu8; CORRECT_SERIALIZED.len()];
+    let mut serializer = net_struct_serde::NetStructSerializer::new(&mut serialized);
+    ATTR.serialize(&mut serializer).unwrap();
+    assert_eq!(serializer.finalize(), CORRECT_SERIALIZED.len());
+    assert_eq!(serialized, CORRECT_SERIALIZED);
+    assert_eq!(encoded_len(&ATTR), Ok(CORRECT_SERIALIZED.len()));
+    let mut deserializer = net_struct_serde::NetStructDeserializer::new(&CORRECT_SERIALIZED);
+    let deserialized = SomeAttr::deserialize(&mut deserializer).unwrap();
+    assert_eq!(ATTR, deserialized);
+    assert_eq!(deserialized._reserved, 0);
+    assert_eq!(deserializer.finalize(), CORRECT_SERIALIZED.len());
+}
+
+#[test]
+fn padding_nested() {
+    const ATTR1: SomeAttr = SomeAttr {
+        kind: 1,
+        _reserved: 0,
+        name_len: 4,
+        name: [b'a', b'b', b'c', b'd', 0, 0, 0, 0],
+        flag: 7,
+    };
+    const ATTR2: SomeAttr = SomeAttr {
+        kind: 2,
+        _reserved: 0,
+        name_len: 0,
+        name: [0; 8],
+        flag: 9,
+    };
+    const MESSAGE: SomeMessage = SomeMessage {
+        version: 1,
+        attrs_len: 2,
+        attrs: [ATTR1, ATTR2, ATTR2, ATTR2],
+    };
+    const CORRECT_SERIALIZED: [u8; 22] = [
+        1, 2, 1, 0, 4, b'a', b'b', b'c', b'd', 0, 7, 0, 0, 0, 2, 0, 0, 0, 9, 0, 0, 0,
+    ];
+    let serialized = to_vec::<32, SomeMessage>(&MESSAGE).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<SomeMessage>(serialized.as_slice()).unwrap();
+    assert_eq!(MESSAGE, deserialized);
+}
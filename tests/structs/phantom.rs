@@ -12,7 +12,7 @@ pub struct SomeStruct {
     pub vec1: [u16; 8],
     pub vec1_bytes: u8,
     pub field2: u8,
-    #[net_struct(phantom)]
+    #[net_struct(phantom, default = SomePhantomStruct { arr: [0xff; 64] })]
     pub _phantom_field: SomePhantomStruct,
 }
 
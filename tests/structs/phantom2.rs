@@ -35,6 +35,7 @@ fn phantom2() {
     let mut deserializer = net_struct_serde::NetStructDeserializer::new(&CORRECT_SERIALIZED);
     let deserialized = SomeStruct::deserialize(&mut deserializer).unwrap();
     assert_eq!(S, deserialized);
+    assert_eq!(deserialized.vec1_bytes, 6);
     assert_eq!(deserializer.finalize(), CORRECT_SERIALIZED.len());
     println!("{:?}", deserialized);
 }
@@ -0,0 +1,36 @@
+use net_struct_serde::{string::*, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct DeviceInfo {
+    pub codec: FixedStr<4, b' ', true>,
+    pub name: CStr<8>,
+    pub firmware: FixedStr<6>,
+    pub port: u16,
+}
+
+#[test]
+fn string() {
+    let d = DeviceInfo {
+        codec: FixedStr::new("H26").unwrap(),
+        name: CStr::new("cam0").unwrap(),
+        firmware: FixedStr::new("1.0.2").unwrap(),
+        port: 554,
+    };
+    const CORRECT_SERIALIZED: [u8; 17] = [
+        b'H', b'2', b'6', b' ', b'c', b'a', b'm', b'0', 0, b'1', b'.', b'0', b'.', b'2', 0, 0x02,
+        0x2a,
+    ];
+    let serialized = to_vec::<32, DeviceInfo>(&d).unwrap();
+    assert_eq!(serialized.as_slice(), CORRECT_SERIALIZED);
+    let deserialized = from_slice::<DeviceInfo>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(d, deserialized);
+    assert_eq!(deserialized.name, "cam0");
+    assert_eq!(deserialized.codec.as_str(), "H26");
+
+    let mut unterminated = CORRECT_SERIALIZED;
+    unterminated[8] = b'1';
+    assert_eq!(
+        from_slice::<DeviceInfo>(unterminated).err(),
+        Some(SerdeErr::NoTerminator)
+    );
+}
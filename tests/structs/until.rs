@@ -0,0 +1,96 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Label {
+    pub len: u8,
+    #[net_struct(vec_len(len))]
+    pub name: [u8; 8],
+}
+
+impl Label {
+    pub const ROOT: Label = Label {
+        len: 0,
+        name: [0; 8],
+    };
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Query {
+    #[net_struct(until = Label::ROOT)]
+    pub labels: [Label; 4],
+    #[net_struct(until = 0)]
+    pub comment: [u8; 8],
+    #[net_struct(until = 0, terminator(include))]
+    pub path: [u8; 4],
+    #[net_struct(until = 0, terminator(peek))]
+    pub options: [u8; 4],
+    #[net_struct(const = 0)]
+    pub end_of_options: u8,
+}
+
+const fn label(len: u8, name: [u8; 8]) -> Label {
+    Label { len, name }
+}
+
+#[test]
+fn until() {
+    const Q: Query = Query {
+        labels: [
+            label(2, *b"ab\0\0\0\0\0\0"),
+            label(3, *b"com\0\0\0\0\0"),
+            Label::ROOT,
+            Label::ROOT,
+        ],
+        comment: *b"hi\0\0\0\0\0\0",
+        path: *b"/\0\0\0",
+        options: [7, 8, 0, 0],
+        end_of_options: 0,
+    };
+    const CORRECT_SERIALIZED: [u8; 17] = [
+        2, b'a', b'b', 3, b'c', b'o', b'm', 0, b'h', b'i', 0, b'/', 0, 7, 8, 0, 0xee,
+    ];
+    let serialized = to_vec::<32, Query>(&Q).unwrap();
+    assert_eq!(serialized.as_slice(), &CORRECT_SERIALIZED[..16]);
+    let deserialized = from_slice::<Query>(CORRECT_SERIALIZED).unwrap();
+    assert_eq!(Q, deserialized);
+    assert_eq!(deserialized.labels[1], Q.labels[1]);
+    assert_eq!(deserialized.path, *b"/\0\0\0");
+    assert_eq!(deserialized.options, [7, 8, 0, 0]);
+    assert_eq!(deserialized.end_of_options, 0);
+
+    // the elements after the terminator are not compared
+    let mut other = Q;
+    other.comment[5] = b'!';
+    assert_eq!(Q, other);
+
+    // no terminator within the capacity
+    let mut unterminated = [0u8; 20];
+    unterminated[..13].copy_from_slice(&CORRECT_SERIALIZED[..13]);
+    unterminated[13..18].copy_from_slice(&[1, 2, 3, 4, 5]);
+    assert_eq!(from_slice::<Query>(unterminated).err(), Some(SerdeErr::OutOfRange));
+    // or within the input
+    let mut truncated = CORRECT_SERIALIZED;
+    truncated[13..16].copy_from_slice(&[1, 2, 3]);
+    assert_eq!(from_slice::<Query>(truncated).err(), Some(SerdeErr::Eof));
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Full {
+    #[net_struct(until = 0)]
+    pub name: [u8; 2],
+    #[net_struct(until = 0, terminator(include))]
+    pub path: [u8; 2],
+}
+
+#[test]
+fn until_full() {
+    // a full array is followed by a consumed terminator
+    let full = Full { name: [1, 2], path: [3, 0] };
+    let serialized = to_vec::<8, Full>(&full).unwrap();
+    assert_eq!(serialized.as_slice(), &[1, 2, 0, 3, 0]);
+    assert_eq!(from_slice::<Full>(serialized.as_slice()), Ok(full));
+
+    // an included terminator needs room in the array
+    let full = Full { name: [1, 2], path: [3, 4] };
+    assert!(to_vec::<8, Full>(&full).is_err());
+}
@@ -0,0 +1,51 @@
+use net_struct_serde::{varint::*, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeMessage {
+    pub id: QuicVarInt,
+    pub payload_len: Leb128<u32>,
+    #[net_struct(vec_len(payload_len, bytes))]
+    pub payload: [u16; 256],
+    pub remaining_len: MqttVarLen,
+    pub der_len: DerLength,
+    #[net_struct(vec_len(der_len))]
+    pub der: [u8; 4],
+}
+
+#[test]
+fn varint() {
+    let mut s = SomeMessage {
+        id: QuicVarInt(15293),
+        payload_len: Leb128(200),
+        payload: [0; 256],
+        remaining_len: MqttVarLen(321),
+        der_len: DerLength(2),
+        der: [0xaa, 0xbb, 0, 0],
+    };
+    s.payload[..100].fill(0x0102);
+    let mut correct_serialized = [0u8; 209];
+    correct_serialized[..4].copy_from_slice(&[0x7b, 0xbd, 0xc8, 0x01]);
+    for b in correct_serialized[4..204].chunks_mut(2) {
+        b.copy_from_slice(&[0x01, 0x02]);
+    }
+    correct_serialized[204..].copy_from_slice(&[0xc1, 0x02, 0x02, 0xaa, 0xbb]);
+    let serialized = to_vec::<256, SomeMessage>(&s).unwrap();
+    assert_eq!(serialized.as_slice(), correct_serialized);
+    let deserialized = from_slice::<SomeMessage>(correct_serialized).unwrap();
+    assert_eq!(s, deserialized);
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct SomeSigned {
+    pub len: i8,
+    #[net_struct(vec_len(len))]
+    pub vec: [u8; 4],
+}
+
+#[test]
+fn negative_len() {
+    assert_eq!(from_slice::<SomeSigned>([0xff, 1, 2]), Err(SerdeErr::OutOfRange));
+    let s = SomeSigned { len: -1, vec: [1, 2, 3, 4] };
+    assert!(to_vec::<8, SomeSigned>(&s).is_err());
+    assert_eq!(from_slice::<SomeSigned>([2, 1, 2]), Ok(SomeSigned { len: 2, vec: [1, 2, 0, 0] }));
+}
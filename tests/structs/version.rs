@@ -0,0 +1,98 @@
+use net_struct_serde::*;
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(version_field = ver)]
+pub struct Message {
+    pub ver: u8,
+    pub id: u16,
+    #[net_struct(until = 1)]
+    pub legacy: u8,
+    #[net_struct(since = 2)]
+    pub flags: u8,
+    #[net_struct(since = 3, default = 0xffff)]
+    pub session: u16,
+    pub payload: u8,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(version_field = ver)]
+pub struct Record {
+    #[net_struct(phantom, default = 1)]
+    pub ver: u32,
+    pub a: u8,
+    #[net_struct(since = 2)]
+    pub b: u16,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(version_field = ver)]
+pub struct Hello {
+    pub ver: u8,
+    #[net_struct(until_version = 1)]
+    pub legacy: [u8; 2],
+    #[net_struct(until = 0, terminator(consume))]
+    pub name: [u8; 4],
+}
+
+#[test]
+fn version() {
+    const V1: Message = Message {
+        ver: 1,
+        id: 0x0102,
+        legacy: 7,
+        flags: 0,
+        session: 0xffff,
+        payload: 9,
+    };
+    const V1_SERIALIZED: [u8; 5] = [1, 1, 2, 7, 9];
+    assert_eq!(to_vec::<32, Message>(&V1).unwrap().as_slice(), V1_SERIALIZED);
+    let deserialized = from_slice::<Message>(V1_SERIALIZED).unwrap();
+    assert_eq!(V1, deserialized);
+    assert_eq!((deserialized.flags, deserialized.session), (0, 0xffff));
+
+    const V3: Message = Message {
+        ver: 3,
+        id: 0x0102,
+        legacy: 0,
+        flags: 0x80,
+        session: 0x0a0b,
+        payload: 9,
+    };
+    const V3_SERIALIZED: [u8; 7] = [3, 1, 2, 0x80, 0x0a, 0x0b, 9];
+    assert_eq!(to_vec::<32, Message>(&V3).unwrap().as_slice(), V3_SERIALIZED);
+    let deserialized = from_slice::<Message>(V3_SERIALIZED).unwrap();
+    assert_eq!(V3, deserialized);
+    assert_eq!(deserialized.legacy, 0);
+
+    // the fields absent in the version are neither serialized nor compared
+    let mut v2 = V3;
+    v2.ver = 2;
+    v2.legacy = 5;
+    assert_eq!(to_vec::<32, Message>(&v2).unwrap().as_slice(), [2, 1, 2, 0x80, 9]);
+    let mut other = v2;
+    other.session = 0;
+    assert_eq!(v2, other);
+
+    // the version is given by the deserializer
+    const R: Record = Record { ver: 2, a: 1, b: 0x0203 };
+    let serialized = to_vec::<32, Record>(&R).unwrap();
+    assert_eq!(serialized.as_slice(), [1, 2, 3]);
+    let mut deserializer = NetStructDeserializer::new(serialized.as_slice()).version(2);
+    assert_eq!(Record::deserialize(&mut deserializer), Ok(R));
+    let deserialized = from_slice::<Record>(serialized).unwrap();
+    assert_eq!((deserialized.ver, deserialized.a, deserialized.b), (1, 1, 0));
+
+    // `until` with `terminator(..)` on an array is its terminator, and `until_version` its last version
+    const H: Hello = Hello {
+        ver: 1,
+        legacy: [5, 6],
+        name: [b'a', b'b', 0, 0],
+    };
+    let serialized = to_vec::<32, Hello>(&H).unwrap();
+    assert_eq!(serialized.as_slice(), [1, 5, 6, b'a', b'b', 0]);
+    assert_eq!(from_slice::<Hello>(serialized), Ok(H));
+    assert_eq!(
+        from_slice::<Hello>([2, b'a', 0]).map(|h| (h.legacy, h.name)),
+        Ok(([0, 0], [b'a', 0, 0, 0]))
+    );
+}
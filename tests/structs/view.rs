@@ -0,0 +1,91 @@
+use core::net::Ipv4Addr;
+use net_struct_serde::{int::U16, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(view)]
+pub struct Point {
+    pub x: i16,
+    pub y: i16,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(view)]
+pub struct Record {
+    #[net_struct(expect = 2)]
+    pub version: u8,
+    pub src: Ipv4Addr,
+    #[net_struct(reserved(1))]
+    pub _reserved: u8,
+    pub name_len: u8,
+    #[net_struct(vec_len(name_len))]
+    pub name: [u8; 8],
+    pub count: U16,
+    #[net_struct(vec_len(count, bytes))]
+    pub values: [u16; 4],
+    pub origin: Point,
+    pub checksum: u32,
+    #[net_struct(phantom)]
+    pub seen: bool,
+}
+
+#[test]
+fn view() {
+    const CORRECT_SERIALIZED: [u8; 25] = [
+        2, 10, 0, 0, 1, 0, 3, b'a', b'b', b'c', 0x00, 0x04, 0x01, 0x02, 0x03, 0x04, 0xff, 0xff,
+        0x00, 0x01, 0xde, 0xad, 0xbe, 0xef, 0x99,
+    ];
+    let view = RecordView::new_checked(&CORRECT_SERIALIZED[..]).unwrap();
+    assert_eq!(view.version(), Ok(2));
+    assert_eq!(view.src(), Ok(Ipv4Addr::new(10, 0, 0, 1)));
+    assert_eq!(view.name(), Ok(&b"abc"[..]));
+    assert_eq!(view.values(), Ok([0x0102, 0x0304, 0, 0]));
+    assert_eq!(view.origin_range(), Ok(16..20));
+    assert_eq!(view.origin().map(|p| (p.x, p.y)), Ok((-1, 1)));
+    assert_eq!(view.checksum(), Ok(0xdeadbeef));
+    assert_eq!(view.encoded_len(), Ok(24));
+    assert_eq!(
+        view.to_struct().map(|r| r.values),
+        Ok([0x0102, 0x0304, 0, 0])
+    );
+    assert_eq!(
+        RecordView::new_checked(&CORRECT_SERIALIZED[..23]).err(),
+        Some(SerdeErr::Eof)
+    );
+    assert_eq!(
+        PointView::new_unchecked([0x00, 0x01]).y(),
+        Err(SerdeErr::Eof)
+    );
+
+    let mut buf = CORRECT_SERIALIZED;
+    let mut view = RecordView::new_unchecked(&mut buf[..]);
+    view.set_version(3).unwrap();
+    assert_eq!(view.version(), Err(SerdeErr::InvalidValue("version")));
+    view.set_version(2).unwrap();
+    view.name_mut().unwrap().copy_from_slice(b"xyz");
+    assert_eq!(view.name(), Ok(&b"xyz"[..]));
+    // a longer name moves the fields after it
+    view.set_name_len(4).unwrap();
+    assert_eq!(view.name(), Ok(&b"xyz\0"[..]));
+    assert_eq!(view.set_name(b"ab"), Err(SerdeErr::OutOfRange));
+    view.set_name(b"abcd").unwrap();
+    assert_eq!(view.count(), Ok(U16::new(0x0401)));
+    assert_eq!(view.checksum(), Err(SerdeErr::OutOfRange));
+    view.set_count(U16::new(2)).unwrap();
+    assert_eq!(view.checksum_range(), Ok(19..23));
+    view.set_values(&[0x0a0b]).unwrap();
+    assert_eq!(view.set_values(&[1, 2]), Err(SerdeErr::OutOfRange));
+    view.set_origin(Point { x: -1, y: 1 }).unwrap();
+    view.set_checksum(0x01020304).unwrap();
+    assert_eq!(view.encoded_len(), Ok(23));
+    let r = view.to_struct().unwrap();
+    assert_eq!(&r.name[..4], b"abcd");
+    assert_eq!(r.values, [0x0a0b, 0, 0, 0]);
+    assert_eq!((r.checksum, r.seen), (0x01020304, false));
+    assert_eq!(
+        buf[..23],
+        [
+            2, 10, 0, 0, 1, 0, 4, b'a', b'b', b'c', b'd', 0x00, 0x02, 0x0a, 0x0b, 0xff, 0xff, 0x00,
+            0x01, 0x01, 0x02, 0x03, 0x04
+        ]
+    );
+}
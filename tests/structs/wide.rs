@@ -0,0 +1,36 @@
+use net_struct_serde::{float::*, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+pub struct Reading {
+    pub uuid: u128,
+    pub delta: i128,
+    pub sequence: usize,
+    pub offset: isize,
+    pub humidity: F16,
+    #[net_struct(flavour = Bf16)]
+    pub temperature: f32,
+}
+
+#[test]
+fn wide() {
+    const R: Reading = Reading {
+        uuid: 0x0011_2233_4455_6677_8899_aabb_ccdd_eeff,
+        delta: -2,
+        sequence: 0x0102,
+        offset: -1,
+        humidity: F16::from_bits(0x3800),
+        temperature: 3.0,
+    };
+    let mut correct_serialized = [0u8; 52];
+    correct_serialized[..16].copy_from_slice(&R.uuid.to_be_bytes());
+    correct_serialized[16..32].copy_from_slice(&(-2i128).to_be_bytes());
+    correct_serialized[38..40].copy_from_slice(&[0x01, 0x02]);
+    correct_serialized[40..48].copy_from_slice(&[0xff; 8]);
+    correct_serialized[48..52].copy_from_slice(&[0x38, 0x00, 0x40, 0x40]);
+    let serialized = to_vec::<64, Reading>(&R).unwrap();
+    assert_eq!(serialized.as_slice(), correct_serialized);
+    let deserialized = from_slice::<Reading>(correct_serialized).unwrap();
+    assert_eq!(R, deserialized);
+    assert_eq!(deserialized.humidity.to_f32(), 0.5);
+    assert_eq!(deserialized.temperature, 3.0);
+}
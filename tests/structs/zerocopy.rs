@@ -0,0 +1,55 @@
+use net_struct_serde::{int::*, string::FixedStr, traits::ZeroCopy, *};
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(zerocopy)]
+#[repr(C)]
+pub struct UdpHeader {
+    pub src_port: U16,
+    pub dst_port: U16,
+    pub len: U16,
+    pub checksum: U16,
+}
+
+#[derive(Copy, Clone, Debug, NetStruct)]
+#[net_struct(zerocopy)]
+#[repr(C, packed)]
+pub struct Tunnel {
+    pub version: u8,
+    pub ttl: i8,
+    pub name: FixedStr<4>,
+    pub session: U32,
+    pub seq: I24,
+    pub mac: [u8; 6],
+    pub udp: UdpHeader,
+}
+
+#[test]
+fn zerocopy() {
+    const CORRECT_SERIALIZED: [u8; 27] = [
+        1, 0xff, b'g', b'r', b'e', 0, 0x01, 0x02, 0x03, 0x04, 0xff, 0xff, 0xfe, 0xaa, 0xbb, 0xcc,
+        0xdd, 0xee, 0xff, 0x13, 0x88, 0x00, 0x35, 0x00, 0x08, 0x00, 0x00,
+    ];
+    assert_eq!(core::mem::size_of::<Tunnel>(), CORRECT_SERIALIZED.len());
+    let mut buf = [0u8; 32];
+    buf[..CORRECT_SERIALIZED.len()].copy_from_slice(&CORRECT_SERIALIZED);
+    let t = Tunnel::ref_from_bytes(&buf).unwrap();
+    assert_eq!((t.version, t.ttl), (1, -1));
+    assert_eq!(t.name, "gre");
+    assert_eq!(t.session.get(), 0x01020304);
+    assert_eq!(t.seq.get(), -2);
+    assert_eq!(t.udp.dst_port.get(), 53);
+    assert_eq!(t.as_bytes(), CORRECT_SERIALIZED);
+    assert_eq!(
+        to_vec::<32, Tunnel>(t).unwrap().as_slice(),
+        CORRECT_SERIALIZED
+    );
+    assert_eq!(from_slice::<Tunnel>(CORRECT_SERIALIZED).as_ref(), Ok(t));
+
+    let udp = UdpHeader::mut_from_bytes(&mut buf[19..]).unwrap();
+    udp.checksum = U16::new(0xbeef);
+    assert_eq!(buf[25..27], [0xbe, 0xef]);
+    assert_eq!(
+        UdpHeader::ref_from_bytes(&CORRECT_SERIALIZED[..7]).err(),
+        Some(SerdeErr::Eof)
+    );
+}
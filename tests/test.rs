@@ -3,3 +3,6 @@ mod enums;
 
 #[cfg(test)]
 mod structs;
+
+#[cfg(test)]
+mod flags;